@@ -4,7 +4,12 @@ mod build_config;
 use build_config::TOOLCHAINS;
 
 fn main() {
-    for toolchain in TOOLCHAINS.iter() {
+    // Several blockchains share a toolchain, which only has to be set up once
+    let mut toolchains = TOOLCHAINS.to_vec();
+    toolchains.sort_unstable();
+    toolchains.dedup();
+
+    for toolchain in toolchains {
         match ensure_toolchain(toolchain) {
             Ok(_) => {}
             Err(e) => {
@@ -1,4 +1,13 @@
 pub const INK_TOOLCHAIN: &str = "nightly-2023-12-16";
 pub const SOROBAN_TOOLCHAIN: &str = "nightly-2024-07-11";
+pub const NEAR_TOOLCHAIN: &str = "nightly-2024-07-11";
+pub const SOLANA_TOOLCHAIN: &str = "nightly-2024-07-11";
+pub const MULTIVERSX_TOOLCHAIN: &str = "nightly-2024-07-11";
 
-pub const TOOLCHAINS: [&str; 2] = [INK_TOOLCHAIN, SOROBAN_TOOLCHAIN];
+pub const TOOLCHAINS: [&str; 5] = [
+    INK_TOOLCHAIN,
+    SOROBAN_TOOLCHAIN,
+    NEAR_TOOLCHAIN,
+    SOLANA_TOOLCHAIN,
+    MULTIVERSX_TOOLCHAIN,
+];
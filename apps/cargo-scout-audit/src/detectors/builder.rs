@@ -1,41 +1,62 @@
 use anyhow::{bail, ensure, Context, Result};
 use cargo::GlobalContext;
-use cargo_metadata::{Metadata, MetadataCommand};
+use cargo_metadata::MetadataCommand;
 use current_platform::CURRENT_PLATFORM;
 use std::path::PathBuf;
 
-use super::{configuration::DetectorsConfiguration, library::Library, source::download_git_repo};
-use crate::scout::blockchain::BlockChain;
+use super::{
+    configuration::DetectorsConfiguration,
+    library::Library,
+    source::{download_git_repo, head_commit},
+};
+use crate::{
+    scout::blockchain::BlockChain,
+    utils::{cargo::LockFlags, paths, progress},
+};
 
 #[derive(Debug)]
 pub struct DetectorBuilder<'a> {
     cargo_config: &'a GlobalContext,
     detectors_config: &'a DetectorsConfiguration,
-    root_metadata: &'a Metadata,
     verbose: bool,
     toolchain: &'a str,
+    jobs: Option<u32>,
+    lock_flags: LockFlags,
+    show_progress: bool,
 }
 
 impl<'a> DetectorBuilder<'a> {
     pub fn new(
         cargo_config: &'a GlobalContext,
         detectors_config: &'a DetectorsConfiguration,
-        root_metadata: &'a Metadata,
         verbose: bool,
         toolchain: &'a str,
+        jobs: Option<u32>,
+        lock_flags: LockFlags,
+        show_progress: bool,
     ) -> Self {
         Self {
             cargo_config,
             detectors_config,
-            root_metadata,
             verbose,
             toolchain,
+            jobs,
+            lock_flags,
+            show_progress,
         }
     }
 
+    #[tracing::instrument(name = "BUILD DETECTORS", skip_all)]
     pub fn build(&self, bc: &BlockChain, used_detectors: &[String]) -> Result<Vec<PathBuf>> {
         let library = self.get_library()?;
-        let library_paths = library.build(bc, self.verbose)?;
+        let progress = progress::counter(
+            self.show_progress && !self.verbose,
+            "Building detectors",
+            library.metadata.packages.len(),
+        );
+        let library_paths = library.build(bc, self.verbose, self.jobs, self.lock_flags, &progress);
+        progress.finish_and_clear();
+        let library_paths = library_paths?;
         self.filter_detectors(&library_paths, used_detectors)
     }
 
@@ -49,6 +70,11 @@ impl<'a> DetectorBuilder<'a> {
             .collect())
     }
 
+    /// Returns the commit of the detectors source, when it is a git checkout.
+    pub fn get_detectors_commit(&self) -> Option<String> {
+        head_commit(&self.get_detector().ok()?)
+    }
+
     fn get_library(&self) -> Result<Library> {
         let detector_root = self.get_detector()?;
         let workspace_path = self.parse_library_path(&detector_root)?;
@@ -109,14 +135,17 @@ impl<'a> DetectorBuilder<'a> {
             })?;
 
         let toolchain = format!("{}-{}", self.toolchain, CURRENT_PLATFORM);
+        let target_dir = paths::libraries_dir(&toolchain).unwrap_or_else(|| {
+            package_metadata
+                .target_directory
+                .clone()
+                .into_std_path_buf()
+        });
 
         Ok(Library::new(
             workspace_path,
             toolchain,
-            self.root_metadata
-                .target_directory
-                .clone()
-                .into_std_path_buf(),
+            target_dir,
             package_metadata,
         ))
     }
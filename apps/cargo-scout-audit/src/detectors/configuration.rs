@@ -1,6 +1,6 @@
-use std::{env, path::Path};
+use std::{env, path::Path, thread, time::Duration};
 
-use crate::scout::blockchain::BlockChain;
+use crate::{scout::blockchain::BlockChain, utils::print::print_warning};
 use anyhow::{anyhow, Context, Result};
 use cargo::{
     core::{Dependency, GitReference, SourceId},
@@ -9,6 +9,9 @@ use cargo::{
 use git2::{RemoteCallbacks, Repository};
 use tempfile::TempDir;
 
+const BRANCH_CHECK_ATTEMPTS: u32 = 3;
+const BRANCH_CHECK_BACKOFF: Duration = Duration::from_millis(500);
+
 #[derive(Debug, Clone)]
 pub struct DetectorsConfiguration {
     pub dependency: Dependency,
@@ -34,18 +37,30 @@ pub fn check_branch_exists(url: &str, branch: &str) -> Result<bool> {
     Ok(branch_exists)
 }
 
-fn create_git_dependency(blockchain: &BlockChain, branch: &str) -> Result<Dependency> {
+// Retries failures to reach the remote, waiting twice as long after each one. A missing branch
+// is an answer, not a failure.
+fn check_branch_exists_with_retry(url: &str, branch: &str) -> Result<bool> {
+    let mut attempt = 1;
+    loop {
+        match check_branch_exists(url, branch) {
+            Err(err) if attempt < BRANCH_CHECK_ATTEMPTS => {
+                tracing::debug!(%err, attempt, branch, "Failed to check detectors branch");
+                thread::sleep(BRANCH_CHECK_BACKOFF * 2u32.pow(attempt - 1));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn create_git_dependency(blockchain: &BlockChain, reference: GitReference) -> Result<Dependency> {
     let url = blockchain
         .get_detectors_url()
         .into_url()
         .with_context(|| format!("Failed to get URL for {} blockchain", blockchain))?;
 
-    Dependency::parse(
-        "library",
-        None,
-        SourceId::for_git(&url, GitReference::Branch(branch.to_string()))?,
-    )
-    .with_context(|| "Failed to create git dependency")
+    Dependency::parse("library", None, SourceId::for_git(&url, reference)?)
+        .with_context(|| "Failed to create git dependency")
 }
 
 /// Returns list of detectors.
@@ -53,7 +68,19 @@ fn create_git_dependency(blockchain: &BlockChain, branch: &str) -> Result<Depend
 pub fn get_remote_detectors_configuration(
     blockchain: BlockChain,
     force_fallback: bool,
+    detectors_ref: Option<&str>,
 ) -> Result<DetectorsConfiguration> {
+    // A pinned commit or tag bypasses the release branch lookup entirely
+    if let Some(detectors_ref) = detectors_ref {
+        return Ok(DetectorsConfiguration {
+            dependency: create_git_dependency(
+                &blockchain,
+                GitReference::Rev(detectors_ref.to_string()),
+            )?,
+            path: Some("detectors".to_string()),
+        });
+    }
+
     let toolchain = blockchain.get_toolchain();
     let scout_version = env!("CARGO_PKG_VERSION");
     let default_branch = format!("release/{}", scout_version);
@@ -64,15 +91,25 @@ pub fn get_remote_detectors_configuration(
         .into_url()
         .with_context(|| format!("Failed to get URL for {} blockchain", blockchain))?;
 
-    let branch = if !force_fallback && check_branch_exists(url.as_str(), &default_branch)? {
+    // A default branch that can't be reached is treated as missing, so the fallback is tried
+    let default_exists = !force_fallback
+        && check_branch_exists_with_retry(url.as_str(), &default_branch).unwrap_or_else(|err| {
+            print_warning(&format!(
+                "Could not check the detectors branch {}, falling back to {}: {}",
+                default_branch, fallback_branch, err
+            ));
+            false
+        });
+
+    let branch = if default_exists {
         default_branch
-    } else if check_branch_exists(url.as_str(), &fallback_branch)? {
+    } else if check_branch_exists_with_retry(url.as_str(), &fallback_branch)? {
         fallback_branch
     } else {
         return Err(anyhow!("Could not find any suitable branch for detectors"));
     };
 
-    let dependency = create_git_dependency(&blockchain, &branch)?;
+    let dependency = create_git_dependency(&blockchain, GitReference::Branch(branch))?;
 
     let detectors = DetectorsConfiguration {
         dependency,
@@ -2,11 +2,15 @@ use std::{env::consts, path::PathBuf};
 
 use anyhow::Result;
 use cargo_metadata::Metadata;
+use indicatif::ProgressBar;
 use itertools::Itertools;
 
 use crate::{
     scout::blockchain::BlockChain,
-    utils::{cargo, env},
+    utils::{
+        cargo::{self, LockFlags},
+        env, progress,
+    },
 };
 /// Represents a Rust library.
 #[derive(Debug, Clone)]
@@ -29,16 +33,14 @@ impl Library {
     }
 
     /// Builds the library and returns its path.
-    pub fn build(&self, bc: &BlockChain, verbose: bool) -> Result<Vec<PathBuf>> {
-        // Build entire workspace
-        cargo::build("detectors", bc, !verbose)
-            .sanitize_environment()
-            .env_remove(env::RUSTFLAGS)
-            .current_dir(&self.root)
-            .args(["--release"])
-            .success()?;
-
-        // Verify all libraries were built
+    pub fn build(
+        &self,
+        bc: &BlockChain,
+        verbose: bool,
+        jobs: Option<u32>,
+        lock_flags: LockFlags,
+        progress: &ProgressBar,
+    ) -> Result<Vec<PathBuf>> {
         let compiled_library_paths = self
             .metadata
             .packages
@@ -47,6 +49,21 @@ impl Library {
             .map(|p| self.path(p.name))
             .collect_vec();
 
+        // Build entire workspace
+        let mut command = cargo::build("detectors", bc, !verbose);
+        command
+            .sanitize_environment()
+            .env_remove(env::RUSTFLAGS)
+            .envs([(env::CARGO_TARGET_DIR, &self.target_dir)])
+            .current_dir(&self.root)
+            .args(["--release"]);
+        if let Some(jobs) = jobs {
+            command.args([format!("--jobs={jobs}")]);
+        }
+        command.args(lock_flags.args());
+        progress::track_outputs(progress, &compiled_library_paths, || command.success())?;
+
+        // Verify all libraries were built
         let unexistant_libraries = compiled_library_paths
             .clone()
             .into_iter()
@@ -56,33 +73,16 @@ impl Library {
             anyhow::bail!("Could not determine if {:?} exist", unexistant_libraries);
         }
 
-        // Copy libraries to target directory
-        let target_dir = self.target_directory();
-        if !target_dir.exists() {
-            std::fs::create_dir_all(&target_dir)?;
-        }
-
         Ok(compiled_library_paths)
     }
 
-    pub fn target_directory(&self) -> PathBuf {
-        self.target_dir
-            .join("scout/libraries")
-            .join(&self.toolchain)
-    }
-
     pub fn path(&self, library_name: String) -> PathBuf {
-        self.metadata
-            .target_directory
-            .clone()
-            .into_std_path_buf()
-            .join("release")
-            .join(format!(
-                "{}{}@{}{}",
-                consts::DLL_PREFIX,
-                library_name.replace('-', "_"),
-                self.toolchain,
-                consts::DLL_SUFFIX
-            ))
+        self.target_dir.join("release").join(format!(
+            "{}{}@{}{}",
+            consts::DLL_PREFIX,
+            library_name.replace('-', "_"),
+            self.toolchain,
+            consts::DLL_SUFFIX
+        ))
     }
 }
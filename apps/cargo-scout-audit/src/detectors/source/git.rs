@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, bail, ensure, Result};
 use cargo::{
@@ -65,3 +65,10 @@ fn git_dependency_root_from_package<'a>(
         unimplemented!()
     }
 }
+
+/// Returns the commit checked out at `path`, if it is inside a git repository.
+pub fn head_commit(path: &Path) -> Option<String> {
+    let repo = git2::Repository::discover(path).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
@@ -1,3 +1,3 @@
 mod git;
 
-pub use git::download_git_repo;
+pub use git::{download_git_repo, head_commit};
@@ -1,19 +1,44 @@
 use cargo_scout_audit::{
+    scout::nightly_runner::forwarded_options,
     startup::{run_scout, CargoSubCommand, Cli},
-    utils::{print::print_error, telemetry},
+    utils::{
+        print::pretty_error,
+        telemetry::{self, LogFile},
+    },
 };
 use clap::Parser;
 
 fn main() {
-    let subscriber = telemetry::get_subscriber("scout".into(), "warn".into(), std::io::stdout);
-    telemetry::init_subscriber(subscriber);
-
     let cli = Cli::parse();
 
     match cli.subcmd {
         CargoSubCommand::ScoutAudit(opts) => {
+            // A nightly child gets its options from the parent, and logs after it
+            let (log_opts, append) = match forwarded_options() {
+                Ok(Some(forwarded)) => (forwarded, true),
+                _ => (opts.clone(), false),
+            };
+            let log_file = match &log_opts.log_file {
+                Some(path) => {
+                    let level = log_opts.log_level.as_deref().unwrap_or("debug");
+                    match LogFile::open(path, level, append) {
+                        Ok(log_file) => Some(log_file),
+                        Err(e) => {
+                            eprintln!("{}", pretty_error(e.to_string().trim()));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                None => None,
+            };
+
+            let subscriber =
+                telemetry::get_subscriber("scout".into(), "warn".into(), std::io::stdout, log_file);
+            telemetry::init_subscriber(subscriber);
+
+            // Fatal errors are printed even with --quiet, which only hides warnings
             if let Err(e) = run_scout(opts) {
-                print_error(e.to_string().trim());
+                eprintln!("{}", pretty_error(e.to_string().trim()));
                 std::process::exit(1);
             }
         }
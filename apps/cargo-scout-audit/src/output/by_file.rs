@@ -0,0 +1,41 @@
+use super::report::Report;
+use crate::scout::findings::lint_id;
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Generates a map from each file to the findings in it, for editors to annotate files.
+pub fn generate_by_file(report: &Report, findings: &[Value]) -> Result<String> {
+    let vulnerabilities = report.vulnerabilities();
+
+    let mut files = BTreeMap::<String, Vec<Value>>::new();
+    for finding in findings.iter() {
+        let Some(id) = lint_id(finding) else {
+            continue;
+        };
+        let Some(span) = finding.get("spans").and_then(|spans| spans.get(0)) else {
+            continue;
+        };
+        let Some(path) = span.get("file_name").and_then(Value::as_str) else {
+            continue;
+        };
+        let vulnerability = vulnerabilities
+            .get(&id)
+            .map(|(_, _, vulnerability)| vulnerability);
+
+        files
+            .entry(path.replace('\\', "/"))
+            .or_default()
+            .push(json!({
+                "lint_id": id,
+                "severity": vulnerability.map(|vulnerability| vulnerability.severity.to_lowercase()),
+                "message": finding.get("message").and_then(Value::as_str).unwrap_or_default(),
+                "line": span.get("line_start"),
+                "column": span.get("column_start"),
+                "end_line": span.get("line_end"),
+                "end_column": span.get("column_end"),
+            }));
+    }
+
+    Ok(serde_json::to_string_pretty(&files)?)
+}
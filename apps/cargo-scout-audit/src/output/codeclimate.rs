@@ -0,0 +1,73 @@
+use super::report::Report;
+use crate::scout::findings::{fingerprint, lint_id};
+use anyhow::Result;
+use serde_json::{json, Value};
+
+// CodeClimate only accepts a fixed set of categories, so vulnerability classes are folded into
+// the closest one.
+fn categories(vulnerability_class: &str) -> Vec<&'static str> {
+    match vulnerability_class.to_lowercase().as_str() {
+        "best practices" => vec!["Style"],
+        "gas usage" | "resource management" => vec!["Performance"],
+        _ => vec!["Security"],
+    }
+}
+
+// Without a span, the finding is placed on the manifest of its crate
+fn manifest_path(report: &Report, finding: &Value) -> Option<String> {
+    let krate = finding.get("crate").and_then(Value::as_str)?;
+    report
+        .summary
+        .executed_on
+        .iter()
+        .find(|package| package.name.replace('-', "_") == krate.replace('-', "_"))
+        .map(|package| package.relative_path.to_string_lossy().replace('\\', "/"))
+}
+
+fn line(span: Option<&Value>, field: &str) -> u64 {
+    span.and_then(|span| span.get(field))
+        .and_then(Value::as_u64)
+        .unwrap_or(1)
+}
+
+/// Generates a CodeClimate engine report from the raw findings.
+pub fn generate_codeclimate(report: &Report, findings: &[Value]) -> Result<String> {
+    let vulnerabilities = report.vulnerabilities();
+
+    let issues: Vec<Value> = findings
+        .iter()
+        .filter_map(|finding| {
+            let id = lint_id(finding)?;
+            let (_, category, vulnerability) = vulnerabilities.get(&id)?;
+            let span = finding.get("spans").and_then(|spans| spans.get(0));
+            // CodeClimate consumers reject issues without a path
+            let path = span
+                .and_then(|span| span.get("file_name"))
+                .and_then(Value::as_str)
+                .map(|path| path.replace('\\', "/"))
+                .or_else(|| manifest_path(report, finding))?;
+
+            Some(json!({
+                "type": "issue",
+                "check_name": id,
+                "description": finding
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or(&vulnerability.short_message),
+                "content": { "body": vulnerability.long_message },
+                "categories": categories(&category.id),
+                "severity": report.severity_map.codeclimate_severity(&vulnerability.severity),
+                "fingerprint": fingerprint(finding),
+                "location": {
+                    "path": path,
+                    "lines": {
+                        "begin": line(span, "line_start"),
+                        "end": line(span, "line_end"),
+                    },
+                },
+            }))
+        })
+        .collect();
+
+    Ok(serde_json::to_string_pretty(&issues)?)
+}
@@ -3,43 +3,89 @@ use crate::{
         raw_report::json_to_string,
         table::{construct_table, prepare_tera_for_table_render_console},
     },
-    utils::detectors_info::LintInfo,
+    scout::findings::lint_id,
+    startup::ConsoleFormat,
+    utils::{detectors_info::LintInfo, print::print_warning},
 };
 use serde_json::Value;
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
 use tera::{Context, Tera};
 use terminal_color_builder::OutputFormatter;
 
 const CONSOLE_TEMPLATE: &str = include_str!("./template.txt");
 
-fn get_template_path() -> (String, String) {
-    (
-        env!("HOME").to_string() + "/.scout-audit/templates",
-        "console.txt".to_string(),
-    )
+// Appends the id to the headline of a rendered diagnostic, e.g. `warning: message [detector]`.
+fn with_detector_id(rendered: &str, id: &str) -> String {
+    match rendered.split_once('\n') {
+        Some((headline, rest)) => format!("{headline} [{id}]\n{rest}"),
+        None => format!("{rendered} [{id}]"),
+    }
+}
+
+// `severity lint-id file:line message`, for logs that are grepped rather than read
+fn short_line(finding: &Value, detectors_info: &HashMap<String, LintInfo>) -> String {
+    let id = lint_id(finding).unwrap_or_default();
+    let severity = detectors_info
+        .get(&id)
+        .map_or("Unknown", |info| info.severity.as_str());
+    let span = finding.get("spans").and_then(|spans| spans.get(0));
+    let location = span
+        .map(|span| {
+            format!(
+                "{}:{}",
+                json_to_string(span.get("file_name").unwrap_or(&Value::Null)),
+                span.get("line_start").unwrap_or(&Value::Null)
+            )
+        })
+        .unwrap_or_default();
+    let message = json_to_string(finding.get("message").unwrap_or(&Value::Null));
+    format!("{} {} {} {}", severity, id, location, message)
+}
+
+fn render_summary(template: &str, table: &Value) -> Result<String, tera::Error> {
+    let mut tera = Tera::default();
+    let mut context = Context::new();
+    tera.add_raw_template("base_template", template)?;
+    prepare_tera_for_table_render_console(&mut tera, &mut context, table, "summary");
+    tera.render("base_template", &context)
 }
 
 pub(crate) fn render_report(
     findings: &[Value],
     crates: &HashMap<String, bool>,
     detectors_info: &HashMap<String, LintInfo>,
+    template_dir: Option<&Path>,
+    show_detector_id: bool,
+    summary_only: bool,
+    format: ConsoleFormat,
 ) -> Result<(), tera::Error> {
-    for finding in findings.iter() {
+    for finding in findings.iter().filter(|_| !summary_only) {
+        if format == ConsoleFormat::Short {
+            println!("{}", short_line(finding, detectors_info));
+            continue;
+        }
         let rendered = json_to_string(finding.get("rendered").unwrap_or(&Value::default()));
-        print!("{rendered}");
+        match lint_id(finding).filter(|_| show_detector_id) {
+            Some(id) => print!("{}", with_detector_id(&rendered, &id)),
+            None => print!("{rendered}"),
+        }
     }
 
     let table = construct_table(findings, crates, detectors_info).to_json_table();
 
-    let mut tera = Tera::default();
-    let mut context = Context::new();
-    tera.add_raw_template(
-        "base_template",
-        &crate::output::utils::get_template(get_template_path, CONSOLE_TEMPLATE),
-    )?;
-    prepare_tera_for_table_render_console(&mut tera, &mut context, &table, "summary");
-
-    let result = tera.render("base_template", &context)?;
+    let template =
+        crate::output::utils::get_template(template_dir, "console.txt", CONSOLE_TEMPLATE);
+    // A broken custom template must not hide the findings summary
+    let result = match render_summary(&template, &table) {
+        Err(e) if template != CONSOLE_TEMPLATE => {
+            print_warning(&format!(
+                "The custom console template failed, using the default one: {}",
+                e
+            ));
+            render_summary(CONSOLE_TEMPLATE, &table)?
+        }
+        result => result?,
+    };
 
     println!("{}", result);
 
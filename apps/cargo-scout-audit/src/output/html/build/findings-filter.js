@@ -0,0 +1,72 @@
+const findingFilters = {
+  category: "all",
+  severities: new Set(),
+  packages: new Set(),
+  query: "",
+};
+
+function checkedValues(selector) {
+  return new Set(
+    Array.from(document.querySelectorAll(selector))
+      .filter((input) => input.checked)
+      .map((input) => input.value)
+  );
+}
+
+function applyFindingFilters() {
+  document.querySelectorAll(".category-section").forEach((section) => {
+    let sectionVisible = false;
+    const categoryMatches =
+      findingFilters.category === "all" ||
+      section.getAttribute("data-category") === findingFilters.category;
+
+    section.querySelectorAll(".vulnerability").forEach((vulnerability) => {
+      let vulnerabilityVisible = false;
+      const severityMatches = findingFilters.severities.has(
+        vulnerability.getAttribute("data-severity")
+      );
+
+      vulnerability.querySelectorAll(".finding-detail").forEach((row) => {
+        const visible =
+          categoryMatches &&
+          severityMatches &&
+          findingFilters.packages.has(row.getAttribute("data-package")) &&
+          row.getAttribute("data-search").includes(findingFilters.query);
+        row.classList.toggle("hidden", !visible);
+        vulnerabilityVisible = vulnerabilityVisible || visible;
+      });
+
+      vulnerability.classList.toggle("hidden", !vulnerabilityVisible);
+      sectionVisible = sectionVisible || vulnerabilityVisible;
+    });
+
+    section.classList.toggle("hidden", !sectionVisible);
+  });
+}
+
+document.addEventListener("DOMContentLoaded", () => {
+  findingFilters.severities = checkedValues(".severity-facet");
+  findingFilters.packages = checkedValues(".package-facet");
+
+  document.querySelectorAll(".severity-facet").forEach((input) => {
+    input.addEventListener("change", () => {
+      findingFilters.severities = checkedValues(".severity-facet");
+      applyFindingFilters();
+    });
+  });
+
+  document.querySelectorAll(".package-facet").forEach((input) => {
+    input.addEventListener("change", () => {
+      findingFilters.packages = checkedValues(".package-facet");
+      applyFindingFilters();
+    });
+  });
+
+  const search = document.getElementById("findingSearch");
+  if (search) {
+    search.addEventListener("input", () => {
+      findingFilters.query = search.value.trim().toLowerCase();
+      applyFindingFilters();
+    });
+  }
+});
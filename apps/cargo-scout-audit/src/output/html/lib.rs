@@ -2,11 +2,11 @@ use crate::output::report::Report;
 
 use super::{tera::HtmlEngine, utils};
 use anyhow::Result;
-use std::{error::Error, fmt::Write, vec};
+use std::{error::Error, fmt::Write, path::Path, vec};
 
 // Generates an HTML report from a given `Report` object.
-pub fn generate_html(report: &Report) -> Result<String> {
-    let tera = HtmlEngine::new()?;
+pub fn generate_html(report: &Report, template_dir: Option<&Path>) -> Result<String> {
+    let tera = HtmlEngine::new(template_dir)?;
 
     // Report context
     let report_context = tera.create_context("report", report);
@@ -14,12 +14,25 @@ pub fn generate_html(report: &Report) -> Result<String> {
     // Analytics context
     let report_analytics = utils::get_analytics(report);
     let analytics_context = tera.create_context("analytics", report_analytics);
-    tera.render_template(vec![report_context, analytics_context])
-        .map_err(|err: tera::Error| {
-            let mut error_msg = format!("Error rendering HTML report:\n -> {}", err);
-            if let Some(source) = err.source() {
-                write!(error_msg, "\n -> Caused by: {}", source).unwrap();
-            }
-            anyhow::anyhow!(error_msg)
-        })
+
+    // Filter facets context
+    let report_facets = utils::get_facets(report);
+    let facets_context = tera.create_context("facets", report_facets);
+
+    // Severity badges context
+    let badges = utils::get_severity_badges(report);
+    let badges_context = tera.create_context("badges", badges);
+    tera.render_template(vec![
+        report_context,
+        analytics_context,
+        facets_context,
+        badges_context,
+    ])
+    .map_err(|err: tera::Error| {
+        let mut error_msg = format!("Error rendering HTML report:\n -> {}", err);
+        if let Some(source) = err.source() {
+            write!(error_msg, "\n -> Caused by: {}", source).unwrap();
+        }
+        anyhow::anyhow!(error_msg)
+    })
 }
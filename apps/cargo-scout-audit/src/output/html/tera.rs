@@ -1,5 +1,7 @@
 extern crate tera;
 
+use crate::output::utils::get_template;
+use std::path::Path;
 use tera::{Context, Result, Tera};
 
 const TEMPLATE_BASE: &str = include_str!("./templates/base.html");
@@ -9,6 +11,7 @@ const TEMPLATE_MODAL: &str = include_str!("./templates/modal.html");
 const TEMPLATE_VULNERABILITY_DETAILS: &str = include_str!("./templates/vulnerability_details.html");
 const JS_MODAL_HANDLER: &str = include_str!("./build/modal-handler.js");
 const JS_CATEGORY_FILTER: &str = include_str!("./build/category-filter.js");
+const JS_FINDINGS_FILTER: &str = include_str!("./build/findings-filter.js");
 const JS_VULNERABILITY_DETAILS: &str = include_str!("./build/vulnerability-details-display.js");
 const JS_VULNERABILITY_EXPANSION: &str = include_str!("./build/vulnerability-expansion.js");
 const STYLES: &str = include_str!("./build/styles.css");
@@ -18,9 +21,8 @@ pub struct HtmlEngine {
 }
 
 impl HtmlEngine {
-    pub fn new() -> Result<Self> {
-        let mut tera = Tera::default();
-        tera.add_raw_templates(vec![
+    pub fn new(template_dir: Option<&Path>) -> Result<Self> {
+        let templates = [
             ("base.html", TEMPLATE_BASE),
             ("modal.html", TEMPLATE_MODAL),
             ("categories.html", TEMPLATE_CATEGORIES),
@@ -28,10 +30,17 @@ impl HtmlEngine {
             ("vulnerability_details.html", TEMPLATE_VULNERABILITY_DETAILS),
             ("modal.js", JS_MODAL_HANDLER),
             ("category-filter.js", JS_CATEGORY_FILTER),
+            ("findings-filter.js", JS_FINDINGS_FILTER),
             ("vulnerability-expansion.js", JS_VULNERABILITY_EXPANSION),
             ("vulnerability-details-display.js", JS_VULNERABILITY_DETAILS),
             ("styles.css", STYLES),
-        ])?;
+        ];
+
+        let templates =
+            templates.map(|(name, template)| (name, get_template(template_dir, name, template)));
+
+        let mut tera = Tera::default();
+        tera.add_raw_templates(templates)?;
         Ok(HtmlEngine { tera })
     }
 
@@ -1,7 +1,28 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+
+use serde::Serialize;
 
 use crate::output::report;
 
+#[derive(Serialize, Debug)]
+pub struct SeverityFacet {
+    pub name: String,
+    pub count: u32,
+}
+
+// Class and icon of a detector's severity, so templates don't match severity labels themselves
+#[derive(Serialize, Debug)]
+pub struct SeverityBadge {
+    pub class: String,
+    pub icon: &'static str,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Facets {
+    pub severities: Vec<SeverityFacet>,
+    pub packages: Vec<String>,
+}
+
 pub fn get_analytics(report: &report::Report) -> HashMap<String, u32> {
     let mut analytics = HashMap::new();
 
@@ -12,3 +33,47 @@ pub fn get_analytics(report: &report::Report) -> HashMap<String, u32> {
 
     analytics
 }
+
+// Badges keyed by vulnerability id. Unknown severities look like enhancements.
+pub fn get_severity_badges(report: &report::Report) -> HashMap<String, SeverityBadge> {
+    report
+        .categories
+        .iter()
+        .flat_map(|category| &category.vulnerabilities)
+        .map(|vulnerability| {
+            let severity = report::Severity::parse(&vulnerability.severity)
+                .unwrap_or(report::Severity::Enhancement);
+            let badge = SeverityBadge {
+                class: severity.label().to_lowercase(),
+                icon: severity.icon(),
+            };
+            (vulnerability.id.clone(), badge)
+        })
+        .collect()
+}
+
+// Collects the values the report can be filtered by on the client side.
+pub fn get_facets(report: &report::Report) -> Facets {
+    let severities = report
+        .severity_map
+        .ordered()
+        .iter()
+        .map(|severity| SeverityFacet {
+            name: severity.label().to_string(),
+            count: *report.summary.by_severity.get(severity).unwrap_or(&0),
+        })
+        .collect();
+
+    let packages = report
+        .findings
+        .iter()
+        .map(|finding| finding.package.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    Facets {
+        severities,
+        packages,
+    }
+}
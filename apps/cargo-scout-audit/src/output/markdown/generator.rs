@@ -9,6 +9,8 @@ use crate::output::{
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SummaryContext {
+    pub title: String,
+    pub author: Option<String>,
     pub categories: Vec<SummaryCategory>,
 }
 
@@ -41,7 +43,11 @@ pub fn generate_summary_context(report: &Report) -> (SummaryContext, serde_json:
     let table = report.summary.table.to_json_map();
 
     (
-        SummaryContext { categories },
+        SummaryContext {
+            title: report.title.clone(),
+            author: report.author.clone(),
+            categories,
+        },
         serde_json::Value::Object(table),
     )
 }
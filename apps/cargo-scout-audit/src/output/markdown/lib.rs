@@ -2,16 +2,22 @@ use super::{generator::generate_summary_context, tera::MdEngine};
 use crate::output::report::Report;
 use crate::output::table::register_functions_for_tera_md;
 use anyhow::{Context, Result};
+use std::path::Path;
 
 // Generates an Markdown report from a given `Report` object.
-pub fn generate_markdown(report: &Report, render_styles: bool) -> Result<String> {
-    let mut tera = MdEngine::new()?;
+pub fn generate_markdown(
+    report: &Report,
+    render_styles: bool,
+    template_dir: Option<&Path>,
+) -> Result<String> {
+    let mut tera = MdEngine::new(template_dir)?;
 
     let (summary, table) = generate_summary_context(report);
 
     let report_context = tera.create_context("report", report);
     let summary_context = tera.create_context("summary", summary);
     let style_context = tera.create_context("render_styles", render_styles);
+    let summary_only_context = tera.create_context("summary_only", report.summary_only);
 
     let summary_table_context = tera.create_context("summary_table", table);
     register_functions_for_tera_md(tera.get_tera_mut());
@@ -23,6 +29,7 @@ pub fn generate_markdown(report: &Report, render_styles: bool) -> Result<String>
             summary_context,
             summary_table_context,
             style_context,
+            summary_only_context,
         ])
         .with_context(|| "Failed to render template 'base_template'")?;
 
@@ -1,4 +1,4 @@
-mod generator;
+pub(crate) mod generator;
 pub mod lib;
 pub use lib::generate_markdown;
 mod tera;
@@ -1,25 +1,19 @@
 extern crate tera;
 
+use std::path::Path;
 use tera::{Context, Result, Tera};
 
 const TEMPLATE_STR: &str = include_str!("./template.md");
 
-fn get_template_path() -> (String, String) {
-    (
-        env!("HOME").to_string() + "/.scout-audit/templates",
-        "md.txt".to_string(),
-    )
-}
-
 #[derive(Debug)]
 pub struct MdEngine {
     tera: Tera,
 }
 
 impl MdEngine {
-    pub fn new() -> Result<Self> {
+    pub fn new(template_dir: Option<&Path>) -> Result<Self> {
         let mut tera = Tera::default();
-        let template = crate::output::utils::get_template(get_template_path, TEMPLATE_STR);
+        let template = crate::output::utils::get_template(template_dir, "md.txt", TEMPLATE_STR);
         tera.add_raw_template("base_template", template.as_str())?;
         Ok(MdEngine { tera })
     }
@@ -1,8 +1,14 @@
+pub mod by_file;
+pub mod codeclimate;
 pub mod console;
 pub mod html;
 pub mod markdown;
 pub mod pdf;
 pub mod raw_report;
 pub mod report;
+pub mod sarif;
+pub mod schema;
+pub mod severity_map;
+pub mod status;
 pub mod table;
 pub mod utils;
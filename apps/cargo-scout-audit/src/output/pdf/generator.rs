@@ -1,5 +1,6 @@
 use crate::output::{
-    report::{Category, Finding, Report},
+    markdown::generator::generate_summary_context,
+    report::{Category, Finding, Report, ReportStatus},
     table::prepare_tera_for_table_render_html,
     utils,
 };
@@ -7,10 +8,16 @@ use std::collections::HashMap;
 use tera::{Context, Tera};
 
 // Generate the header for the report
-pub fn generate_header(date: String) -> String {
+pub fn generate_header(report: &Report) -> String {
+    let title = tera::escape_html(&report.title);
+    let author = report
+        .author
+        .as_ref()
+        .map(|author| format!("<p>Prepared by {}</p>\n", tera::escape_html(author)))
+        .unwrap_or_default();
     format!(
         "<!DOCTYPE html>\n<html>\n<head>\n\
-        <title>Scout Report - {}</title>\n\
+        <title>Scout Report - {} - {}</title>\n\
         <style>\n\
         body {{ font-family: 'Arial', sans-serif; line-height: 1.6; }}\n\
         img.banner {{ width: 100%; height: auto; }}\n\
@@ -21,10 +28,19 @@ pub fn generate_header(date: String) -> String {
         td {{ word-wrap: break-word; }}\n\
         ul.summary {{ list-style: none; padding: 0; }}\n\
         ul.summary li a {{ text-decoration: none; color: #333; }}\n\
+        ol.toc li {{ margin: 4px 0; }}\n\
+        ol.toc li a {{ text-decoration: none; color: #333; }}\n\
+        h2.category {{ page-break-before: always; }}\n\
+        .badge {{ display: inline-block; padding: 1px 8px; border-radius: 8px; color: #fff; font-size: 0.8em; }}\n\
+        .badge-critical {{ background-color: #e3342f; }}\n\
+        .badge-medium {{ background-color: #f6993f; }}\n\
+        .badge-minor {{ background-color: #38c172; }}\n\
+        .badge-enhancement {{ background-color: #3490dc; }}\n\
         </style>\n\
         </head>\n<body>\n\
-        <h1>Scout Report - {}</h1>\n",
-        date, date
+        <h1>Scout Report - {} - {}</h1>\n\
+        {}",
+        title, report.date, title, report.date, author
     )
 }
 
@@ -42,9 +58,38 @@ fn generate_table(report: &Report) -> Result<String, ()> {
     tera.render("base_template", &context).map_err(|_| ())
 }
 
+// Generate a severity badge
+fn generate_badge(severity: &str) -> String {
+    format!(
+        "<span class=\"badge badge-{}\">{}</span>",
+        severity.to_lowercase(),
+        utils::capitalize(severity)
+    )
+}
+
+// Generate the table of contents, linking to the summary and every category section
+pub fn generate_table_of_contents(report: &Report) -> String {
+    let (summary, _) = generate_summary_context(report);
+
+    let mut toc_html = String::from("<h2>Table of Contents</h2>\n<ol class=\"toc\">\n");
+    toc_html.push_str("<li><a href=\"#summary\">Summary</a></li>\n");
+    for category in &summary.categories {
+        toc_html.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a> ({} results) {}</li>\n",
+            category.link,
+            category.name,
+            category.results_count,
+            generate_badge(&category.severity)
+        ));
+    }
+    toc_html.push_str("</ol>\n");
+
+    toc_html
+}
+
 // Generate the summary for the report
 pub fn generate_summary(report: &Report) -> String {
-    let mut summary_html = String::from("<h2>Summary</h2>");
+    let mut summary_html = String::from("<h2 id=\"summary\">Summary</h2>");
 
     summary_html.push_str(
         generate_table(report)
@@ -69,6 +114,22 @@ pub fn generate_summary(report: &Report) -> String {
     }
     summary_html.push_str("</ul>");
 
+    match report.status {
+        ReportStatus::Clean => summary_html.push_str("<p>No issues found.</p>"),
+        ReportStatus::Incomplete => summary_html.push_str(
+            "<p><strong>Warning:</strong> Some crates were not analyzed because they failed to compile, so the results may be incomplete.</p>",
+        ),
+        ReportStatus::Findings => {}
+    }
+
+    if report.omitted_findings > 0 {
+        summary_html.push_str(&format!(
+            "<p><strong>Warning:</strong> {} findings omitted. Only the {} most severe findings are shown.</p>",
+            report.omitted_findings,
+            report.findings.len()
+        ));
+    }
+
     summary_html
 }
 
@@ -109,12 +170,19 @@ pub fn generate_body(categories: &[Category], findings: &[Finding]) -> String {
 
 // Function to generate HTML for a category
 fn generate_category(category: &Category) -> String {
-    let mut html_category = format!("<h2>{}</h2>\n", category.id);
+    let mut html_category = format!(
+        "<h2 class=\"category\" id=\"{}\">{}</h2>\n",
+        utils::sanitize_category_name(&category.name),
+        category.id
+    );
     for vulnerability in &category.vulnerabilities {
-        html_category.push_str(&format!("<h3>{}</h3>\n", vulnerability.name));
+        html_category.push_str(&format!(
+            "<h3>{} <code>[{}]</code></h3>\n",
+            vulnerability.name, vulnerability.id
+        ));
         html_category.push_str(&format!(
             "<p><strong>Impact:</strong> {}</p>\n",
-            utils::capitalize(&vulnerability.severity)
+            generate_badge(&vulnerability.severity)
         ));
         html_category.push_str(&format!(
             "<p><strong>Description:</strong> {}</p>\n",
@@ -131,22 +199,34 @@ fn generate_category(category: &Category) -> String {
 // Function to generate a table for a category
 fn generate_table_for_category(category: &Category, findings: &[Finding]) -> String {
     let table_header = "<table style=\"width: 100%; table-layout: fixed;\">\n<thead>\n<tr>\
-                        <th style=\"width: 20%;\">ID</th>\
-                        <th style=\"width: 30%;\">Package</th>\
-                        <th style=\"width: 50%;\">Detection</th>\
+                        <th style=\"width: 15%;\">ID</th>\
+                        <th style=\"width: 15%;\">Severity</th>\
+                        <th style=\"width: 25%;\">Package</th>\
+                        <th style=\"width: 45%;\">Detection</th>\
                         </tr>\n</thead>\n<tbody>\n";
     let table_body: String = findings
         .iter()
         .filter(|finding| finding.category_id == category.id)
-        .map(generate_finding)
+        .map(|finding| {
+            let severity = category
+                .vulnerabilities
+                .iter()
+                .find(|v| v.id == finding.vulnerability_id)
+                .map(|v| v.severity.as_str())
+                .unwrap_or_default();
+            generate_finding(finding, severity)
+        })
         .collect();
     format!("{}{}</tbody>\n</table>\n", table_header, table_body)
 }
 
 // Function to generate HTML for a finding
-fn generate_finding(finding: &Finding) -> String {
+fn generate_finding(finding: &Finding, severity: &str) -> String {
     format!(
-        "<tr>\n<td>{}</td>\n<td>{}</td>\n<td>{}</td>\n</tr>\n",
-        finding.id, finding.package, finding.span
+        "<tr>\n<td>{}</td>\n<td>{}</td>\n<td>{}</td>\n<td>{}</td>\n</tr>\n",
+        finding.id,
+        generate_badge(severity),
+        finding.package,
+        finding.span
     )
 }
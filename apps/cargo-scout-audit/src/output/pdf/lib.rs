@@ -1,4 +1,6 @@
-use super::generator::{generate_body, generate_header, generate_summary};
+use super::generator::{
+    generate_body, generate_header, generate_summary, generate_table_of_contents,
+};
 use crate::output::report::Report;
 use anyhow::{Context, Result};
 use headless_chrome::{Browser, LaunchOptionsBuilder};
@@ -11,7 +13,10 @@ fn generate_temp_html(report: &Report) -> Result<NamedTempFile> {
     let mut report_html = String::new();
 
     // Header
-    report_html.push_str(&generate_header(report.date.clone()));
+    report_html.push_str(&generate_header(report));
+
+    // Table of contents
+    report_html.push_str(&generate_table_of_contents(report));
 
     // Summary
     report_html.push_str(&generate_summary(report));
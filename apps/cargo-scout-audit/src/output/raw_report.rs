@@ -1,5 +1,8 @@
 use super::report::{Category, Finding, Report, Severity, Summary, Vulnerability};
-use crate::{scout::project_info::ProjectInfo, utils::detectors_info::LintInfo};
+use crate::{
+    scout::{findings::clean_detectors, project_info::ProjectInfo},
+    utils::detectors_info::LintInfo,
+};
 use anyhow::{Context, Result};
 use serde_json::Value;
 use std::io::{BufReader, Read, Seek, SeekFrom};
@@ -28,13 +31,17 @@ impl RawReport {
         let categories = generate_categories(detector_info, &findings)
             .context("Failed to generate categories")?;
         let summary = create_summary(detector_info, info, &findings, json_findings, crates);
-        Ok(Report::new(
+        let mut report = Report::new(
             info.name.clone(),
             info.date.clone(),
             summary,
             categories,
             findings,
-        ))
+            clean_detectors(detector_info.keys(), json_findings),
+            crates,
+        );
+        report.version_control = info.version_control.clone();
+        Ok(report)
     }
 }
 
@@ -93,7 +100,9 @@ fn process_findings(
             )
         })?;
 
+        let (line, code_context) = extract_code_context(&absolute_path, finding);
         let error_message = parse_error_message(finding);
+        let crates = parse_crates(finding);
 
         let occurrence_index = det_map.entry(category.clone()).or_insert(0);
         *occurrence_index += 1;
@@ -106,8 +115,13 @@ fn process_findings(
             error_message,
             span,
             code_snippet,
+            line,
+            code_context,
             package,
             file_path: relative_path,
+            crates,
+            blockchain: json_to_string_opt(finding.get("blockchain")),
+            toolchain: json_to_string_opt(finding.get("toolchain")),
         });
     }
 
@@ -201,6 +215,58 @@ fn extract_code_snippet(file_path: &Path, finding: &Value) -> Result<String> {
     String::from_utf8(buffer).with_context(|| "Failed to convert extracted bytes to UTF-8 string")
 }
 
+fn extract_code_context(file_path: &Path, finding: &Value) -> (u64, String) {
+    let Some(sp) = finding.get("spans").and_then(|spans| spans.get(0)) else {
+        return (0, String::new());
+    };
+    let field = |name: &str| sp.get(name).and_then(Value::as_u64).unwrap_or(0) as usize;
+    let (line_start, line_end) = (field("line_start"), field("line_end"));
+    let (column_start, column_end) = (field("column_start"), field("column_end"));
+    if line_start == 0 || line_end < line_start {
+        return (0, String::new());
+    }
+
+    let Ok(source) = std::fs::read_to_string(file_path) else {
+        return (line_start as u64, String::new());
+    };
+
+    let gutter = line_end.to_string().len();
+    let mut ret = String::new();
+    for (index, text) in source
+        .lines()
+        .enumerate()
+        .skip(line_start - 1)
+        .take(line_end - line_start + 1)
+    {
+        let number = index + 1;
+        let text = text.trim_end();
+        let chars = text.chars().count();
+        // Columns are 1-based and count characters, not bytes
+        let start = if number == line_start {
+            column_start.saturating_sub(1)
+        } else {
+            chars - text.trim_start().chars().count()
+        };
+        let end = if number == line_end {
+            column_end.saturating_sub(1).min(chars)
+        } else {
+            chars
+        };
+
+        ret.push_str(&format!("{:>gutter$} | {}\n", number, text));
+        if end > start {
+            ret.push_str(&format!(
+                "{:>gutter$} | {}{}\n",
+                "",
+                " ".repeat(start),
+                "^".repeat(end - start)
+            ));
+        }
+    }
+
+    (line_start as u64, ret)
+}
+
 fn parse_error_message(finding: &Value) -> String {
     finding
         .get("message")
@@ -209,6 +275,15 @@ fn parse_error_message(finding: &Value) -> String {
         .to_string()
 }
 
+fn parse_crates(finding: &Value) -> Vec<String> {
+    match finding.get("crates") {
+        Some(Value::Array(crates)) => crates.iter().map(json_to_string).collect(),
+        _ => json_to_string_opt(finding.get("crate"))
+            .into_iter()
+            .collect(),
+    }
+}
+
 fn generate_categories(
     detector_info: &HashMap<String, LintInfo>,
     findings: &[Finding],
@@ -237,7 +312,13 @@ fn generate_categories(
         }
     }
 
-    Ok(categories.into_values().collect())
+    let mut categories: Vec<Category> = categories.into_values().collect();
+    categories.sort_by(|a, b| a.id.cmp(&b.id));
+    for category in categories.iter_mut() {
+        category.vulnerabilities.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+
+    Ok(categories)
 }
 
 fn create_summary(
@@ -249,25 +330,17 @@ fn create_summary(
 ) -> Summary {
     let total_vulnerabilities = findings.len() as u32;
 
-    let mut by_severity: HashMap<Severity, u32> = [
-        (Severity::Critical, 0),
-        (Severity::Medium, 0),
-        (Severity::Minor, 0),
-        (Severity::Enhancement, 0),
-    ]
-    .iter()
-    .cloned()
-    .collect();
+    let mut by_severity: HashMap<Severity, u32> = Severity::ALL
+        .into_iter()
+        .map(|severity| (severity, 0))
+        .collect();
 
     for finding in findings {
-        if let Some(lint_info) = detector_info.get(&finding.vulnerability_id) {
-            match lint_info.severity.as_ref() {
-                "Critical" => *by_severity.get_mut(&Severity::Critical).unwrap() += 1,
-                "Medium" => *by_severity.get_mut(&Severity::Medium).unwrap() += 1,
-                "Minor" => *by_severity.get_mut(&Severity::Minor).unwrap() += 1,
-                "Enhancement" => *by_severity.get_mut(&Severity::Enhancement).unwrap() += 1,
-                _ => continue,
-            };
+        let severity = detector_info
+            .get(&finding.vulnerability_id)
+            .and_then(|lint_info| Severity::parse(&lint_info.severity));
+        if let Some(severity) = severity {
+            *by_severity.entry(severity).or_default() += 1;
         }
     }
 
@@ -1,25 +1,86 @@
-use super::{html, markdown, pdf, utils};
-use crate::output::raw_report::json_to_string;
+use super::markdown::generator::{generate_summary_context, SummaryCategory};
+use super::severity_map::SeverityMap;
+use super::{by_file, codeclimate, html, markdown, pdf, sarif, utils};
 use crate::output::table::Table;
 use crate::startup::OutputFormat;
-use crate::utils::detectors_info::LintInfo;
+use crate::utils::detectors_info::{Confidence, LintInfo};
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
-use std::fs::File;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 pub struct Report {
     pub name: String,
+    // The project name, unless `--report-title` is given
+    #[serde(default)]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
     pub date: String,
+    // Lets tools tell a clean run apart from one that produced no output
+    #[serde(default)]
+    pub status: ReportStatus,
     pub summary: Summary,
     pub categories: Vec<Category>,
     pub findings: Vec<Finding>,
+    #[serde(default)]
+    pub clean_detectors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detectors_ref: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_detectors: Vec<SkippedDetector>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<FindingsDiff>,
+    // Findings silenced with a `scout-audit:ignore` comment
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed_findings: Vec<SuppressedFinding>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_control: Option<VersionControl>,
+    // Findings left out of the report by `--max-findings`
+    #[serde(default)]
+    pub omitted_findings: usize,
+    // Only set with `--embed-metadata`, so the report describes how it was produced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ReportMetadata>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detector_coverage: Option<DetectorCoverage>,
+    #[serde(skip)]
+    pub severity_map: SeverityMap,
+    // Set by `--summary-only`, the json and markdown reports leave the findings out
+    #[serde(skip)]
+    pub summary_only: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportStatus {
+    Clean,
+    #[default]
+    Findings,
+    // Nothing was analyzed, or some crate failed to compile, so findings may be missing
+    Incomplete,
+}
+
+impl ReportStatus {
+    pub fn from_findings<T>(findings: &[T], crates: &HashMap<String, bool>) -> Self {
+        if crates.is_empty() || crates.values().any(|compiled| !compiled) {
+            ReportStatus::Incomplete
+        } else if findings.is_empty() {
+            ReportStatus::Clean
+        } else {
+            ReportStatus::Findings
+        }
+    }
+}
+
+// Declared from the most to the least severe, so sorting puts the most severe first
+#[derive(
+    Serialize, Deserialize, JsonSchema, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Critical,
@@ -28,7 +89,46 @@ pub enum Severity {
     Enhancement,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl Severity {
+    pub const ALL: [Severity; 4] = [
+        Severity::Critical,
+        Severity::Medium,
+        Severity::Minor,
+        Severity::Enhancement,
+    ];
+
+    /// The label detectors and profiles use, e.g. `Critical`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Critical => "Critical",
+            Severity::Medium => "Medium",
+            Severity::Minor => "Minor",
+            Severity::Enhancement => "Enhancement",
+        }
+    }
+
+    /// Icon the HTML report shows next to findings of this severity.
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Critical => "fa-exclamation-circle",
+            Severity::Medium => "fa-exclamation-triangle",
+            Severity::Minor | Severity::Enhancement => "fa-info-circle",
+        }
+    }
+
+    /// Parses a built-in severity label, ignoring case.
+    pub fn parse(label: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|severity| severity.label().eq_ignore_ascii_case(label.trim()))
+    }
+
+    pub fn labels() -> String {
+        Self::ALL.map(|severity| severity.label()).join(", ")
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 pub struct Summary {
     pub executed_on: Vec<Package>,
     pub total_vulnerabilities: u32,
@@ -36,21 +136,21 @@ pub struct Summary {
     pub table: Table,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct Package {
     pub name: String,
     pub relative_path: PathBuf,
     pub absolute_path: PathBuf,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 pub struct Category {
     pub id: String,
     pub name: String,
     pub vulnerabilities: Vec<Vulnerability>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 pub struct Vulnerability {
     pub id: String,
     pub name: String,
@@ -58,9 +158,16 @@ pub struct Vulnerability {
     pub long_message: String,
     pub severity: String,
     pub help: String,
+    pub doc_url: String,
+    #[serde(default)]
+    pub confidence: Confidence,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwe: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swc: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 pub struct Finding {
     pub id: u32,
     pub occurrence_index: u32,
@@ -69,8 +176,116 @@ pub struct Finding {
     pub error_message: String,
     pub span: String,
     pub code_snippet: String,
+    #[serde(default)]
+    pub line: u64,
+    // Source lines of the span, with the span underlined as rustc does
+    #[serde(default)]
+    pub code_context: String,
     pub package: String,
     pub file_path: String,
+    pub crates: Vec<String>,
+    // Set when findings of several blockchains are merged with `--all-blockchains`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockchain: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<String>,
+}
+
+// Git checkout the analyzed project comes from
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct VersionControl {
+    pub repository_uri: Option<String>,
+    pub revision: String,
+    pub branch: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct SkippedDetector {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct DiffEntry {
+    pub fingerprint: String,
+    pub vulnerability_id: String,
+    pub location: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct SuppressedFinding {
+    pub vulnerability_id: String,
+    pub location: String,
+    pub message: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
+pub struct FindingsDiff {
+    pub compared_to: String,
+    pub new: Vec<DiffEntry>,
+    pub fixed: Vec<DiffEntry>,
+    pub unchanged: Vec<DiffEntry>,
+}
+
+// How many of the available detectors were run, once filters and profiles narrowed them down
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone, Copy)]
+pub struct DetectorCoverage {
+    pub used: usize,
+    pub available: usize,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct ReportToolchain {
+    pub blockchain: String,
+    pub toolchain: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
+pub struct ReportMetadata {
+    pub scout_version: String,
+    pub toolchains: Vec<ReportToolchain>,
+    pub workspace_root: PathBuf,
+    // Every loaded detector, not only those with findings
+    pub detectors: BTreeMap<String, LintInfo>,
+}
+
+// The json report written with `--summary-only`
+#[derive(Serialize)]
+struct SummaryReport<'a> {
+    name: &'a str,
+    date: &'a str,
+    status: ReportStatus,
+    summary: &'a Summary,
+    categories: Vec<SummaryCategory>,
+}
+
+impl<'a> SummaryReport<'a> {
+    fn new(report: &'a Report) -> Self {
+        let (summary, _) = generate_summary_context(report);
+        SummaryReport {
+            name: &report.name,
+            date: &report.date,
+            status: report.status,
+            summary: &report.summary,
+            categories: summary.categories,
+        }
+    }
+}
+
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, value| !value.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(values) => {
+            values.retain(|value| !value.is_null());
+            values.iter_mut().for_each(strip_nulls);
+        }
+        _ => {}
+    }
 }
 
 impl From<&LintInfo> for Vulnerability {
@@ -82,10 +297,29 @@ impl From<&LintInfo> for Vulnerability {
             long_message: lint_info.long_message.clone(),
             severity: lint_info.severity.clone(),
             help: lint_info.help.clone(),
+            doc_url: lint_info.doc_url.clone().unwrap_or_default(),
+            confidence: lint_info.confidence,
+            cwe: lint_info.cwe.clone(),
+            swc: lint_info.swc.clone(),
         }
     }
 }
 
+/// The reported detectors with their categories, in report order, looked up by detector id.
+pub struct ReportedVulnerabilities<'a> {
+    pub entries: Vec<(&'a Category, &'a Vulnerability)>,
+    indices: HashMap<&'a str, usize>,
+}
+
+impl<'a> ReportedVulnerabilities<'a> {
+    /// Position of the detector in `entries`, with its category and vulnerability.
+    pub fn get(&self, id: &str) -> Option<(usize, &'a Category, &'a Vulnerability)> {
+        let index = *self.indices.get(id)?;
+        let (category, vulnerability) = self.entries[index];
+        Some((index, category, vulnerability))
+    }
+}
+
 impl Report {
     pub fn new(
         name: String,
@@ -93,16 +327,51 @@ impl Report {
         summary: Summary,
         categories: Vec<Category>,
         findings: Vec<Finding>,
+        clean_detectors: Vec<String>,
+        crates: &HashMap<String, bool>,
     ) -> Self {
         Report {
+            status: ReportStatus::from_findings(&findings, crates),
+            title: name.clone(),
+            author: None,
             name,
             date,
             summary,
             categories,
             findings,
+            clean_detectors,
+            detectors_ref: None,
+            skipped_detectors: Vec::new(),
+            diff: None,
+            suppressed_findings: Vec::new(),
+            version_control: None,
+            omitted_findings: 0,
+            metadata: None,
+            detector_coverage: None,
+            severity_map: SeverityMap::default(),
+            summary_only: false,
         }
     }
 
+    pub fn vulnerabilities(&self) -> ReportedVulnerabilities<'_> {
+        let entries: Vec<(&Category, &Vulnerability)> = self
+            .categories
+            .iter()
+            .flat_map(|category| {
+                category
+                    .vulnerabilities
+                    .iter()
+                    .map(move |vulnerability| (category, vulnerability))
+            })
+            .collect();
+        let indices = entries
+            .iter()
+            .enumerate()
+            .map(|(index, (_, vulnerability))| (vulnerability.id.as_str(), index))
+            .collect();
+        ReportedVulnerabilities { entries, indices }
+    }
+
     #[tracing::instrument(name = "SAVING REPORT TO FILE", level = "debug", skip_all, fields(path = %path.display()))]
     pub fn save_to_file(&self, path: &PathBuf, content: String) -> Result<()> {
         utils::write_to_file(path, content.as_bytes())?;
@@ -110,21 +379,41 @@ impl Report {
     }
 
     #[tracing::instrument(name = "GENERATING HTML FROM REPORT", level = "debug", skip_all)]
-    pub fn generate_html(&self) -> Result<String> {
-        html::generate_html(self)
+    pub fn generate_html(&self, template_dir: Option<&Path>) -> Result<String> {
+        html::generate_html(self, template_dir)
     }
 
     #[tracing::instrument(name = "GENERATING MARKDOWN FROM REPORT", level = "debug", skip_all)]
-    pub fn generate_markdown(&self, render_styles: bool) -> Result<String> {
-        markdown::generate_markdown(self, render_styles)
+    pub fn generate_markdown(
+        &self,
+        render_styles: bool,
+        template_dir: Option<&Path>,
+    ) -> Result<String> {
+        markdown::generate_markdown(self, render_styles, template_dir)
     }
 
     #[tracing::instrument(name = "GENERATING JSON FROM REPORT", level = "debug", skip_all)]
     pub fn generate_json(&self) -> Result<String> {
-        let json = serde_json::to_string_pretty(self)?;
+        let json = if self.summary_only {
+            serde_json::to_string_pretty(&SummaryReport::new(self))?
+        } else {
+            serde_json::to_string_pretty(self)?
+        };
         Ok(json)
     }
 
+    #[tracing::instrument(name = "GENERATING TOML FROM REPORT", level = "debug", skip_all)]
+    pub fn generate_toml(&self) -> Result<String> {
+        // Same structure as the JSON report, without the nulls TOML can't represent
+        let mut value = if self.summary_only {
+            serde_json::to_value(SummaryReport::new(self))?
+        } else {
+            serde_json::to_value(self)?
+        };
+        strip_nulls(&mut value);
+        toml::to_string_pretty(&value).with_context(|| "Failed to serialize the report as TOML")
+    }
+
     #[tracing::instrument(name = "GENERATING PDF FROM REPORT", level = "debug", skip_all)]
     pub fn generate_pdf(&self, path: &Path) -> Result<()> {
         pdf::generate_pdf(path, self)
@@ -135,95 +424,83 @@ impl Report {
         findings: &Vec<Value>,
         output_path: Option<PathBuf>,
         output_format: &OutputFormat,
+        template_dir: Option<&Path>,
     ) -> Result<Option<PathBuf>> {
+        let path = output_path.unwrap_or_else(|| output_format.default_path());
+        // Not every format goes through `save_to_file`
+        if path != Path::new("-") {
+            if let Some(parent) = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+            {
+                fs::create_dir_all(parent).with_context(|| {
+                    format!("Failed to create output directory: {}", parent.display())
+                })?;
+            }
+        }
         match output_format {
             OutputFormat::Html => {
                 // Generate HTML report
-                let html = self.generate_html()?;
+                let html = self.generate_html(template_dir)?;
 
                 // Save to file
-                let html_path = output_path.unwrap_or_else(|| PathBuf::from("report.html"));
-                self.save_to_file(&html_path, html)?;
-
-                // Open the HTML report in the default web browser
-                webbrowser::open(
-                    html_path
-                        .to_str()
-                        .with_context(|| "Path conversion to string failed")?,
-                )
-                .with_context(|| "Failed to open HTML report")?;
-
-                Ok(Some(html_path))
+                self.save_to_file(&path, html)?;
             }
             OutputFormat::Json => {
                 // Generate JSON report
                 let json = self.generate_json()?;
 
                 // Save to file
-                let json_path = output_path.unwrap_or_else(|| PathBuf::from("report.json"));
-                self.save_to_file(&json_path, json)?;
-
-                Ok(Some(json_path))
+                self.save_to_file(&path, json)?;
+            }
+            OutputFormat::Toml => {
+                let toml = self.generate_toml()?;
+                self.save_to_file(&path, toml)?;
             }
             OutputFormat::RawJson => {
-                let json_path = output_path.unwrap_or_else(|| PathBuf::from("raw-report.json"));
-                let mut json_file = File::create(&json_path)?;
+                let mut json_file = File::create(&path)?;
 
                 for finding in findings.iter() {
                     std::io::Write::write(&mut json_file, finding.to_string().as_bytes())?;
                     std::io::Write::write(&mut json_file, b"\n")?;
                 }
-
-                Ok(Some(json_path))
             }
             OutputFormat::Markdown => {
                 // Generate Markdown
-                let markdown = self.generate_markdown(true)?;
+                let markdown = self.generate_markdown(true, template_dir)?;
 
                 // Save to file
-                let md_path = output_path.unwrap_or_else(|| PathBuf::from("report.md"));
-                self.save_to_file(&md_path, markdown)?;
-
-                Ok(Some(md_path))
+                self.save_to_file(&path, markdown)?;
             }
             OutputFormat::MarkdownGithub => {
                 // Generate Markdown
-                let markdown = self.generate_markdown(false)?;
+                let markdown = self.generate_markdown(false, template_dir)?;
 
                 // Save to file
-                let md_path = output_path.unwrap_or_else(|| PathBuf::from("report.md"));
-                self.save_to_file(&md_path, markdown)?;
-
-                Ok(Some(md_path))
+                self.save_to_file(&path, markdown)?;
             }
             OutputFormat::Sarif => {
-                let sarif_path = output_path.unwrap_or_else(|| PathBuf::from("report.sarif"));
-
-                let mut sarif_file = File::create(&sarif_path)?;
-
-                let child = std::process::Command::new("clippy-sarif")
-                    .stdin(std::process::Stdio::piped())
-                    .stdout(std::process::Stdio::piped())
-                    .spawn()?;
-
-                for finding in findings {
-                    let rendered =
-                        json_to_string(finding.get("rendered").unwrap_or(&Value::default()));
-                    std::io::Write::write_all(
-                        &mut child.stdin.as_ref().unwrap(),
-                        rendered.as_bytes(),
-                    )?;
-                }
-
-                std::io::Write::write_all(&mut sarif_file, &child.wait_with_output()?.stdout)?;
-
-                Ok(Some(sarif_path))
+                let sarif = sarif::generate_sarif(self, findings)?;
+                self.save_to_file(&path, sarif)?;
             }
             OutputFormat::Pdf => {
-                let pdf_path = output_path.unwrap_or_else(|| PathBuf::from("report.pdf"));
-                self.generate_pdf(&pdf_path)?;
-                Ok(Some(pdf_path))
+                self.generate_pdf(&path)?;
+            }
+            OutputFormat::Ndjson => {
+                // Findings were already streamed while the analysis ran
+                if path == Path::new("-") {
+                    return Ok(None);
+                }
+            }
+            OutputFormat::CodeClimate => {
+                let json = codeclimate::generate_codeclimate(self, findings)?;
+                self.save_to_file(&path, json)?;
+            }
+            OutputFormat::JsonByFile => {
+                let json = by_file::generate_by_file(self, findings)?;
+                self.save_to_file(&path, json)?;
             }
         }
+        Ok(Some(path))
     }
 }
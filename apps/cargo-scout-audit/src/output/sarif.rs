@@ -0,0 +1,180 @@
+use super::report::{Category, Report, Vulnerability};
+use crate::scout::findings::{fingerprint, lint_id};
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+pub(crate) const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+// Tags an identifier such as `CWE-682` or `682` the way GitHub code scanning expects,
+// e.g. `external/cwe/cwe-682`
+fn taxonomy_tag(taxonomy: &str, id: &str) -> String {
+    let id = id.trim().to_lowercase();
+    let number = id
+        .strip_prefix(taxonomy)
+        .map_or(id.as_str(), |rest| rest.trim_start_matches(['-', '_', ' ']));
+    format!("external/{taxonomy}/{taxonomy}-{number}")
+}
+
+fn rule(report: &Report, category: &Category, vulnerability: &Vulnerability) -> Value {
+    let mut tags = vec![category.name.clone()];
+    tags.extend(
+        [("cwe", &vulnerability.cwe), ("swc", &vulnerability.swc)]
+            .into_iter()
+            .filter_map(|(taxonomy, id)| Some(taxonomy_tag(taxonomy, id.as_deref()?))),
+    );
+    let mut rule = json!({
+        "id": vulnerability.id,
+        "name": vulnerability.name,
+        "shortDescription": { "text": vulnerability.short_message },
+        "fullDescription": { "text": vulnerability.long_message },
+        "help": { "text": vulnerability.help },
+        "defaultConfiguration": {
+            "level": report.severity_map.sarif_level(&vulnerability.severity),
+        },
+        "properties": {
+            "tags": tags,
+            "severity": vulnerability.severity,
+        },
+    });
+    // GitHub rejects rules whose `helpUri` is not a valid URI
+    if !vulnerability.doc_url.is_empty() {
+        rule["helpUri"] = Value::String(vulnerability.doc_url.clone());
+    }
+    rule
+}
+
+fn region(span: &Value) -> Value {
+    let mut region = Map::new();
+    for (sarif, rustc) in [
+        ("startLine", "line_start"),
+        ("startColumn", "column_start"),
+        ("endLine", "line_end"),
+        ("endColumn", "column_end"),
+    ] {
+        if let Some(value) = span.get(rustc).and_then(Value::as_u64) {
+            region.insert(sarif.into(), value.into());
+        }
+    }
+    Value::Object(region)
+}
+
+/// Generates a SARIF 2.1.0 log from the raw findings, with one rule per reported detector.
+pub fn generate_sarif(report: &Report, findings: &[Value]) -> Result<String> {
+    let vulnerabilities = report.vulnerabilities();
+
+    let rules: Vec<Value> = vulnerabilities
+        .entries
+        .iter()
+        .map(|(category, vulnerability)| rule(report, category, vulnerability))
+        .collect();
+
+    // Findings without a span or a reported detector are kept, only with less detail
+    let results: Vec<Value> = findings
+        .iter()
+        .map(|finding| {
+            let id = lint_id(finding);
+            let reported = id.as_deref().and_then(|id| vulnerabilities.get(id));
+            let vulnerability = reported.map(|(_, _, vulnerability)| vulnerability);
+            let severity =
+                vulnerability.map_or("", |vulnerability| vulnerability.severity.as_str());
+            let text = finding
+                .get("message")
+                .and_then(Value::as_str)
+                .or(vulnerability.map(|vulnerability| vulnerability.short_message.as_str()))
+                .unwrap_or_default();
+            let mut result = json!({
+                "level": report.severity_map.sarif_level(severity),
+                "message": { "text": text },
+                "partialFingerprints": { "scoutFingerprint/v1": fingerprint(finding) },
+            });
+            if let Some(id) = &id {
+                result["ruleId"] = Value::String(id.clone());
+            }
+            if let Some((index, _, _)) = reported {
+                result["ruleIndex"] = index.into();
+            }
+            let span = finding.get("spans").and_then(|spans| spans.get(0));
+            let path = span
+                .and_then(|span| span.get("file_name"))
+                .and_then(Value::as_str);
+            if let (Some(span), Some(path)) = (span, path) {
+                result["locations"] = json!([{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": path.replace('\\', "/"),
+                            "uriBaseId": "%SRCROOT%",
+                        },
+                        "region": region(span),
+                    },
+                }]);
+            }
+            result
+        })
+        .collect();
+
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": "scout",
+                "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rules,
+            },
+        },
+        "results": results,
+        "properties": { "status": report.status },
+    });
+    // GitHub groups runs of the same category into one alert history
+    let category: String = report
+        .name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let run_id = report
+        .version_control
+        .as_ref()
+        .map_or(report.date.as_str(), |vcs| vcs.revision.as_str())
+        .replace('/', "-");
+    run["automationDetails"] = json!({ "id": format!("scout/{}/{}", category, run_id) });
+    // With the scout version, the detectors commit tells exactly what produced the results
+    if let Some(detectors_ref) = &report.detectors_ref {
+        run["tool"]["driver"]["properties"] = json!({ "detectorsRef": detectors_ref });
+    }
+    if let Some(vcs) = &report.version_control {
+        if let Some(uri) = &vcs.repository_uri {
+            let mut provenance = json!({
+                "repositoryUri": uri,
+                "revisionId": vcs.revision,
+                "mappedTo": { "uriBaseId": "%SRCROOT%" },
+            });
+            if let Some(branch) = &vcs.branch {
+                provenance["branch"] = Value::String(branch.clone());
+            }
+            run["versionControlProvenance"] = json!([provenance]);
+        }
+    }
+    if report.omitted_findings > 0 {
+        run["invocations"] = json!([{
+            "executionSuccessful": true,
+            "toolExecutionNotifications": [{
+                "level": "warning",
+                "message": {
+                    "text": format!(
+                        "{} findings omitted. Only the {} most severe findings are reported.",
+                        report.omitted_findings,
+                        results.len()
+                    ),
+                },
+            }],
+        }]);
+    }
+
+    let sarif = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [run],
+    });
+
+    Ok(serde_json::to_string_pretty(&sarif)?)
+}
@@ -0,0 +1,47 @@
+use super::{report::Report, sarif::SARIF_SCHEMA};
+use crate::startup::OutputFormat;
+use anyhow::{bail, Result};
+use serde_json::{json, to_string_pretty};
+
+// Describes the shape of each finding line in a `raw-json` report: the rustc JSON diagnostic
+// emitted by the detector, plus the crate it was found in.
+fn raw_json_schema() -> serde_json::Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "RawFinding",
+        "description": "One line of a raw-json report. Each line is a rustc JSON diagnostic produced by a detector.",
+        "type": "object",
+        "required": ["code", "message", "spans"],
+        "properties": {
+            "code": {
+                "type": "object",
+                "properties": { "code": { "type": "string", "description": "Detector id." } }
+            },
+            "message": { "type": "string" },
+            "level": { "type": "string" },
+            "rendered": { "type": "string" },
+            "spans": { "type": "array", "items": { "type": "object" } },
+            "crate": { "type": "string", "description": "Crate the finding was reported in." },
+            "crates": {
+                "type": "array",
+                "items": { "type": "string" },
+                "description": "Every crate the finding was reported in."
+            }
+        }
+    })
+}
+
+/// Returns a JSON Schema for the given output format.
+pub fn get_schema(format: &OutputFormat) -> Result<String> {
+    let schema = match format {
+        OutputFormat::Json => serde_json::to_value(schemars::schema_for!(Report))?,
+        OutputFormat::RawJson => raw_json_schema(),
+        OutputFormat::Sarif => json!({
+            "$ref": SARIF_SCHEMA,
+            "description": "Scout writes standard SARIF 2.1.0 logs. Detector ids are reported as rule ids and each finding as a result.",
+        }),
+        _ => bail!("Only the json, raw-json and sarif output formats have a schema."),
+    };
+
+    Ok(to_string_pretty(&schema)?)
+}
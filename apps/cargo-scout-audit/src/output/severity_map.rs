@@ -0,0 +1,165 @@
+use super::report::Severity;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::Path};
+
+const SARIF_LEVELS: [&str; 4] = ["none", "note", "warning", "error"];
+const CODECLIMATE_SEVERITIES: [&str; 5] = ["info", "minor", "major", "critical", "blocker"];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SeverityMapFile {
+    #[serde(default)]
+    sarif: HashMap<String, String>,
+    #[serde(default)]
+    codeclimate: HashMap<String, String>,
+    // Custom labels, as in `{ "high": "critical" }`
+    #[serde(default)]
+    labels: HashMap<String, String>,
+    // From the most to the least severe, as in `["medium", "critical", "minor", "enhancement"]`
+    #[serde(default)]
+    order: Option<Vec<String>>,
+}
+
+/// Levels each report format uses for scout severities, the custom severity labels an
+/// organization maps onto them, and which severities rank above the others.
+#[derive(Debug, Clone)]
+pub struct SeverityMap {
+    sarif: HashMap<Severity, String>,
+    codeclimate: HashMap<Severity, String>,
+    labels: HashMap<String, Severity>,
+    order: Vec<Severity>,
+}
+
+impl Default for SeverityMap {
+    fn default() -> Self {
+        let map = |levels: [&str; 4]| {
+            Severity::ALL
+                .into_iter()
+                .zip(levels)
+                .map(|(severity, level)| (severity, level.to_string()))
+                .collect()
+        };
+        SeverityMap {
+            sarif: map(["error", "warning", "note", "note"]),
+            codeclimate: map(["critical", "major", "minor", "info"]),
+            labels: HashMap::new(),
+            order: Severity::ALL.to_vec(),
+        }
+    }
+}
+
+fn parse_severity(severity: &str, section: &str) -> Result<Severity> {
+    Severity::parse(severity).with_context(|| {
+        format!(
+            "Unknown severity `{}` in the {} severity map, expected one of: {}",
+            severity,
+            section,
+            Severity::labels()
+        )
+    })
+}
+
+fn merge(
+    format: &str,
+    defaults: &mut HashMap<Severity, String>,
+    overrides: HashMap<String, String>,
+    levels: &[&str],
+) -> Result<()> {
+    for (severity, level) in overrides {
+        let severity = parse_severity(&severity, format)?;
+        if !levels.contains(&level.as_str()) {
+            bail!(
+                "Invalid {} level `{}` for severity `{}`, expected one of: {}",
+                format,
+                level,
+                severity.label(),
+                levels.join(", ")
+            );
+        }
+        defaults.insert(severity, level);
+    }
+    Ok(())
+}
+
+// Every built-in severity has to be ranked once, so none of them drops out of the reports
+fn parse_order(order: &[String]) -> Result<Vec<Severity>> {
+    let mut ordered = Vec::new();
+    for severity in order {
+        let severity = parse_severity(severity, "order")?;
+        if ordered.contains(&severity) {
+            bail!(
+                "The severity `{}` is ranked twice in the severity map order",
+                severity.label()
+            );
+        }
+        ordered.push(severity);
+    }
+    if ordered.len() != Severity::ALL.len() {
+        bail!(
+            "The severity map order has to rank all of: {}",
+            Severity::labels()
+        );
+    }
+    Ok(ordered)
+}
+
+impl SeverityMap {
+    /// Reads a JSON severity map. Severities it leaves out keep their default level.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read severity map: {}", path.display()))?;
+        let overrides: SeverityMapFile = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse severity map: {}", path.display()))?;
+
+        let mut map = SeverityMap::default();
+        merge("sarif", &mut map.sarif, overrides.sarif, &SARIF_LEVELS)?;
+        merge(
+            "codeclimate",
+            &mut map.codeclimate,
+            overrides.codeclimate,
+            &CODECLIMATE_SEVERITIES,
+        )?;
+        for (label, severity) in overrides.labels {
+            if Severity::parse(&label).is_some() {
+                bail!("The built-in severity `{}` can't be relabeled", label);
+            }
+            map.labels
+                .insert(label.to_lowercase(), parse_severity(&severity, "labels")?);
+        }
+        if let Some(order) = overrides.order {
+            map.order = parse_order(&order)?;
+        }
+        Ok(map)
+    }
+
+    /// The built-in severities from the most to the least severe.
+    pub fn ordered(&self) -> &[Severity] {
+        &self.order
+    }
+
+    /// Where a severity ranks in the map's order, 0 being the most severe.
+    pub fn rank(&self, severity: Severity) -> usize {
+        self.order
+            .iter()
+            .position(|ranked| *ranked == severity)
+            .unwrap_or(self.order.len())
+    }
+
+    /// Resolves a built-in or custom severity label, ignoring case.
+    pub fn severity(&self, label: &str) -> Option<Severity> {
+        Severity::parse(label).or_else(|| self.labels.get(&label.trim().to_lowercase()).copied())
+    }
+
+    pub fn sarif_level(&self, severity: &str) -> &str {
+        self.severity(severity)
+            .and_then(|severity| self.sarif.get(&severity))
+            .map_or("note", String::as_str)
+    }
+
+    pub fn codeclimate_severity(&self, severity: &str) -> &str {
+        self.severity(severity)
+            .and_then(|severity| self.codeclimate.get(&severity))
+            .map_or("info", String::as_str)
+    }
+}
@@ -0,0 +1,83 @@
+use super::report::Severity;
+use super::utils::write_to_file;
+use crate::{scout::findings::lint_id, utils::detectors_info::LintInfo};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::{collections::HashMap, fs, path::PathBuf, time::Duration};
+
+/// Outcome of a run, written to `--status-file` for tools that can't parse stdout.
+#[derive(Serialize, Debug, Default)]
+pub struct RunStatus {
+    pub total_findings: u32,
+    pub by_severity: HashMap<String, u32>,
+    pub crates: HashMap<String, bool>,
+    pub exit_status: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip)]
+    pub detectors: usize,
+}
+
+// Peak resident set size of this process in kB, as long as the platform has `/proc`.
+fn peak_rss_kb() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse()
+        .ok()
+}
+
+impl RunStatus {
+    pub fn record_findings(
+        &mut self,
+        findings: &[Value],
+        crates: &HashMap<String, bool>,
+        detectors_info: &HashMap<String, LintInfo>,
+    ) {
+        self.total_findings = findings.len() as u32;
+        self.crates = crates.clone();
+        self.detectors = detectors_info.len();
+        self.by_severity = Severity::ALL
+            .iter()
+            .map(|severity| (severity.label().to_lowercase(), 0))
+            .collect();
+
+        for finding in findings.iter() {
+            let severity = lint_id(finding)
+                .and_then(|id| detectors_info.get(&id))
+                .and_then(|info| Severity::parse(&info.severity));
+            if let Some(severity) = severity {
+                *self
+                    .by_severity
+                    .entry(severity.label().to_lowercase())
+                    .or_default() += 1;
+            }
+        }
+    }
+
+    /// Prints how long the run took, its peak memory and how much it analyzed. They go to stderr,
+    /// for stdout may carry streamed findings or the editor's JSON messages.
+    pub fn print_stats(&self, elapsed: Duration) {
+        eprintln!("Elapsed time: {:.2}s", elapsed.as_secs_f64());
+        match peak_rss_kb() {
+            Some(kb) => eprintln!("Peak memory: {:.1} MB", kb as f64 / 1024.0),
+            None => eprintln!("Peak memory: unavailable on this platform"),
+        }
+        eprintln!(
+            "Analyzed {} crates with {} detectors.",
+            self.crates.len(),
+            self.detectors
+        );
+    }
+
+    pub fn write(&self, path: &PathBuf) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        write_to_file(path, json.as_bytes())
+            .with_context(|| format!("Failed to write status file: {}", path.display()))
+    }
+}
@@ -1,5 +1,9 @@
-use crate::{output::raw_report::json_to_string_opt, utils::detectors_info::LintInfo};
+use crate::{
+    output::{raw_report::json_to_string_opt, report::Severity},
+    utils::{detectors_info::LintInfo, print::color_enabled},
+};
 use itertools::Itertools;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
@@ -12,7 +16,7 @@ use std::{
 use tera::{Context, Tera};
 use terminal_color_builder::OutputFormatter;
 
-#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, Serialize, Deserialize, JsonSchema, Debug)]
 pub enum Color {
     Default,
     Red,
@@ -40,7 +44,7 @@ impl Color {
     }
 }
 
-#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, Serialize, Deserialize, JsonSchema, Debug)]
 #[allow(unused)]
 pub enum Importance {
     Default,
@@ -68,7 +72,7 @@ impl Importance {
     }
 }
 
-#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, Serialize, Deserialize, JsonSchema, Debug)]
 #[allow(unused)]
 pub enum SemanticColor {
     Default,
@@ -87,18 +91,18 @@ impl SemanticColor {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 pub struct Cell {
     pub content: String,
     pub color: SemanticColor,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 pub struct Row {
     cells: Vec<Cell>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 pub struct Table {
     header: Row,
     rows: Vec<Row>,
@@ -252,7 +256,7 @@ impl Table {
             print!("| ");
             let string = {
                 let mut formatter = OutputFormatter::new();
-                if let SemanticColor::Color(c) = cell.color {
+                if let (SemanticColor::Color(c), true) = (cell.color, color_enabled()) {
                     formatter = match c {
                         Color::Default => formatter,
                         Color::Red => formatter.fg().red(),
@@ -386,7 +390,12 @@ fn process_object_console(o: &serde_json::Map<String, Value>, pad: bool) -> Stri
     }
     let content = content.unwrap();
     let mut formatter = OutputFormatter::new();
-    formatter = match get_color(o) {
+    let color = if color_enabled() {
+        get_color(o)
+    } else {
+        Color::Default
+    };
+    formatter = match color {
         Color::Default => formatter,
         Color::Red => formatter.fg().red(),
         Color::Green => formatter.fg().green(),
@@ -651,7 +660,6 @@ fn count_findings(
 ) -> [usize; 4] {
     let mut ret = [0_usize; 4];
 
-    let mut ignored = 0;
     for finding in findings.iter() {
         let krate = json_to_string_opt(finding.get("crate"));
         if krate.is_none() || krate.unwrap() != *crate_to_find {
@@ -667,18 +675,35 @@ fn count_findings(
             continue;
         }
         let detector = detector.unwrap();
-        *match detector.severity.as_str() {
-            "Critical" => &mut ret[0],
-            "Medium" => &mut ret[1],
-            "Minor" => &mut ret[2],
-            "Enhancement" => &mut ret[3],
-            _ => &mut ignored,
-        } += 1;
+        if let Some(severity) = Severity::parse(&detector.severity) {
+            ret[severity as usize] += 1;
+        }
     }
 
     ret
 }
 
+// Highlights non-zero counts, and the crate name with its most severe finding.
+fn color_by_severity(row: &mut Row, counts: [usize; 4]) {
+    let importances = [
+        Importance::Error,
+        Importance::Warning,
+        Importance::Info,
+        Importance::Default,
+    ];
+    let mut worst = None;
+    for (i, (count, importance)) in counts.iter().zip(importances).enumerate() {
+        if *count == 0 {
+            continue;
+        }
+        row.get_mut(i + 2).color = SemanticColor::Importance(importance);
+        worst = worst.or(Some(importance));
+    }
+    if let Some(importance) = worst {
+        row.get_mut(0).color = SemanticColor::Importance(importance);
+    }
+}
+
 pub(crate) fn construct_table(
     findings: &[Value],
     crates: &HashMap<String, bool>,
@@ -687,10 +712,10 @@ pub(crate) fn construct_table(
     let mut header = Row::from_strs(&[
         "Crate",
         "Status",
-        "Critical",
-        "Medium",
-        "Minor",
-        "Enhancement",
+        Severity::Critical.label(),
+        Severity::Medium.label(),
+        Severity::Minor.label(),
+        Severity::Enhancement.label(),
     ]);
     header.set_color(SemanticColor::Importance(Importance::Header));
     let mut ret = Table::new(header);
@@ -706,7 +731,7 @@ pub(crate) fn construct_table(
         }
         .to_string();
 
-        let row = if !success {
+        let mut row = if !success {
             let mut row = Row::from_strings(&[
                 krate.clone(),
                 success_string,
@@ -727,6 +752,9 @@ pub(crate) fn construct_table(
                 format!("{enhan}"),
             ])
         };
+        if success {
+            color_by_severity(&mut row, [crit, med, min, enhan]);
+        }
         ret.add_row(row);
     }
 
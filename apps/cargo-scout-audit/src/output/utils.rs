@@ -1,10 +1,17 @@
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
 use std::{
+    env,
     fs::{self, File},
-    io::{self, Read, Write},
-    path::PathBuf,
-    str::FromStr,
+    io::{self, Write},
+    path::{Path, PathBuf},
 };
 
+lazy_static! {
+    static ref ENV_VAR_REGEX: Regex = Regex::new(r"\$\{(\w+)\}").expect("Invalid regex");
+}
+
 // Writes data to a file at the specified path, creating the path if it doesn't exist.
 pub fn write_to_file(path: &PathBuf, data: &[u8]) -> io::Result<()> {
     // Ensure the directory exists
@@ -32,6 +39,34 @@ pub fn write_to_file(path: &PathBuf, data: &[u8]) -> io::Result<()> {
     }
 }
 
+// Replaces each `${VAR}` in `path` with the value of the environment variable `VAR`.
+pub fn expand_env_vars(path: &Path) -> Result<PathBuf> {
+    let Some(raw) = path.to_str() else {
+        return Ok(path.to_path_buf());
+    };
+
+    let mut expanded = String::with_capacity(raw.len());
+    let mut last = 0;
+    for captures in ENV_VAR_REGEX.captures_iter(raw) {
+        let (Some(whole), Some(name)) = (captures.get(0), captures.get(1)) else {
+            continue;
+        };
+        let value = env::var(name.as_str()).with_context(|| {
+            format!(
+                "The environment variable `{}` in the output path {} is not set",
+                name.as_str(),
+                raw
+            )
+        })?;
+        expanded.push_str(&raw[last..whole.start()]);
+        expanded.push_str(&value);
+        last = whole.end();
+    }
+    expanded.push_str(&raw[last..]);
+
+    Ok(PathBuf::from(expanded))
+}
+
 pub fn capitalize(s: &str) -> String {
     s.chars()
         .enumerate()
@@ -43,31 +78,16 @@ pub fn sanitize_category_name(name: &str) -> String {
     name.to_lowercase().replace(' ', "-")
 }
 
-fn exists(path: &String) -> bool {
-    std::path::Path::new(path).exists()
+fn read_template(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok()
 }
 
-pub fn get_template<F: FnOnce() -> (String, String)>(get_path: F, template: &str) -> String {
-    let (dir, file) = get_path();
-    if !exists(&dir) {
-        let _ = fs::create_dir_all(&dir);
-    }
-    let path = dir + file.as_str();
-    if exists(&path) {
-        let path = PathBuf::from_str(path.as_str());
-        if let Ok(path) = path {
-            let _ = crate::output::utils::write_to_file(&path, template.as_bytes());
-        }
-        return template.to_string();
-    }
-    let file = fs::File::open(path);
-    if file.is_err() {
-        return template.to_string();
-    }
-    let mut file = file.unwrap();
-    let mut ret = String::new();
-    if file.read_to_string(&mut ret).is_err() {
-        return template.to_string();
-    }
-    ret
+// Loads `file` from `template_dir` (or the user data directory when none is given), falling back
+// to the embedded `template`.
+pub fn get_template(template_dir: Option<&Path>, file: &str, template: &str) -> String {
+    template_dir
+        .map(Path::to_path_buf)
+        .or_else(crate::utils::paths::templates_dir)
+        .and_then(|dir| read_template(&dir.join(file)))
+        .unwrap_or_else(|| template.to_string())
 }
@@ -0,0 +1,121 @@
+use super::{
+    blockchain::BlockChain,
+    bundle::FindingsBundle,
+    findings::lint_id,
+    project_info::{crate_matcher, normalize_crate_name},
+};
+use crate::{output::report::ReportToolchain, startup::Scout};
+use anyhow::{bail, Context, Result};
+use cargo_metadata::Metadata;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Runs each blockchain's detectors on its packages through `scout`, one blockchain after the
+/// other, and merges their findings along with the toolchains that found them. The bundle has
+/// no project info, which the caller adds.
+pub(crate) fn analyze_all_blockchains(
+    opts: &Scout,
+    metadata: &Metadata,
+    mut scout: impl FnMut(Scout) -> Result<FindingsBundle>,
+) -> Result<FindingsBundle> {
+    let selected: HashSet<String> = opts
+        .package
+        .iter()
+        .map(|p| normalize_crate_name(p))
+        .collect();
+    let only_crates = crate_matcher(&opts.only_crates)?;
+    let mut analyzed = Vec::new();
+
+    let blockchains = BlockChain::get_blockchains_by_package(metadata);
+    if blockchains.is_empty() {
+        bail!("Could not find any supported blockchain dependency in the workspace packages.");
+    }
+
+    for (blockchain, packages) in blockchains {
+        let packages: Vec<String> = packages
+            .into_iter()
+            .filter(|p| selected.is_empty() || selected.contains(&normalize_crate_name(p)))
+            .filter(|p| only_crates(p.as_str()))
+            .collect();
+        if packages.is_empty() {
+            continue;
+        }
+        let toolchain = blockchain.get_toolchain();
+        if !opts.quiet {
+            println!(
+                "Analyzing {} with the {} detectors and the {} toolchain.",
+                packages.join(", "),
+                blockchain,
+                toolchain
+            );
+        }
+
+        let mut blockchain_opts = opts.clone();
+        blockchain_opts.all_blockchains = false;
+        blockchain_opts.blockchain = Some(blockchain.to_string());
+        blockchain_opts.package = packages;
+        blockchain_opts.output_format = Vec::new();
+
+        let bundle = scout(blockchain_opts).with_context(|| {
+            format!(
+                "Scout failed when analyzing {} with the {} toolchain",
+                blockchain, toolchain
+            )
+        })?;
+        // Options that answer without analyzing have nothing to merge
+        if bundle.output.is_none() {
+            analyzed.push((blockchain, toolchain, bundle));
+        }
+    }
+
+    // Detectors such as divide_before_multiply exist for several blockchains, each with its own
+    // severity and documentation, so an id run by more than one of them is namespaced by its
+    // blockchain, as in `soroban::divide_before_multiply`
+    let mut chains_by_id = HashMap::<String, usize>::new();
+    for (_, _, bundle) in &analyzed {
+        for id in bundle.detectors.keys() {
+            *chains_by_id.entry(id.clone()).or_default() += 1;
+        }
+    }
+    let shared = |id: &str| chains_by_id.get(id).is_some_and(|chains| *chains > 1);
+
+    let mut merged = FindingsBundle::default();
+    for (blockchain, toolchain, bundle) in analyzed {
+        let namespaced = |id: &str| {
+            if shared(id) {
+                format!("{}::{}", blockchain.to_string().to_lowercase(), id)
+            } else {
+                id.to_string()
+            }
+        };
+
+        for mut finding in bundle.findings {
+            if let Some(id) = lint_id(&finding) {
+                finding["code"]["code"] = Value::String(namespaced(&id));
+            }
+            finding["blockchain"] = Value::String(blockchain.to_string());
+            finding["toolchain"] = Value::String(toolchain.to_string());
+            merged.findings.push(finding);
+        }
+        merged.available_detectors += bundle.available_detectors;
+        for (id, mut info) in bundle.detectors {
+            let id = namespaced(&id);
+            info.id.clone_from(&id);
+            merged.detectors.insert(id, info);
+        }
+        merged
+            .suppressed
+            .extend(bundle.suppressed.into_iter().map(|mut suppressed| {
+                suppressed.vulnerability_id = namespaced(&suppressed.vulnerability_id);
+                suppressed
+            }));
+        merged.crates.extend(bundle.crates);
+        merged.skipped.extend(bundle.skipped);
+        merged.toolchains.push(ReportToolchain {
+            blockchain: blockchain.to_string(),
+            toolchain: toolchain.to_string(),
+        });
+    }
+
+    Ok(merged)
+}
@@ -1,15 +1,21 @@
-use crate::build_config::{INK_TOOLCHAIN, SOROBAN_TOOLCHAIN};
+use crate::build_config::{
+    INK_TOOLCHAIN, MULTIVERSX_TOOLCHAIN, NEAR_TOOLCHAIN, SOLANA_TOOLCHAIN, SOROBAN_TOOLCHAIN,
+};
 use anyhow::{anyhow, Result};
 use cargo_metadata::Metadata;
 use std::collections::HashSet;
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter, EnumString};
 
-#[derive(Debug, Copy, Clone, EnumIter, Display, EnumString)]
+#[derive(Debug, Copy, Clone, PartialEq, EnumIter, Display, EnumString)]
+#[strum(ascii_case_insensitive)]
 pub enum BlockChain {
     Ink,
     Soroban,
     SubstratePallet,
+    Near,
+    Solana,
+    MultiversX,
 }
 
 impl BlockChain {
@@ -22,14 +28,40 @@ impl BlockChain {
             BlockChain::Ink => "https://github.com/CoinFabrik/scout",
             BlockChain::Soroban => "https://github.com/CoinFabrik/scout-soroban",
             BlockChain::SubstratePallet => "https://github.com/CoinFabrik/scout-substrate",
+            BlockChain::Near => "https://github.com/CoinFabrik/scout-near",
+            BlockChain::Solana => "https://github.com/CoinFabrik/scout-solana",
+            BlockChain::MultiversX => "https://github.com/CoinFabrik/scout-multiversx",
         }
     }
 
+    pub fn get_detector_doc_url(&self, detector: &str) -> String {
+        format!(
+            "{}/tree/main/detectors/{}",
+            self.get_detectors_url(),
+            detector.replace('_', "-")
+        )
+    }
+
     pub fn get_toolchain(&self) -> &str {
         match self {
             BlockChain::Ink => INK_TOOLCHAIN,
             BlockChain::Soroban => SOROBAN_TOOLCHAIN,
             BlockChain::SubstratePallet => INK_TOOLCHAIN,
+            BlockChain::Near => NEAR_TOOLCHAIN,
+            BlockChain::Solana => SOLANA_TOOLCHAIN,
+            BlockChain::MultiversX => MULTIVERSX_TOOLCHAIN,
+        }
+    }
+
+    // Whether contracts for this blockchain are checked against the wasm target instead of the
+    // host one. Solana programs target SBF, which is only available in Solana's own toolchain,
+    // so they are checked natively like pallets.
+    pub fn requires_wasm_target(&self) -> bool {
+        match self {
+            BlockChain::Ink | BlockChain::Soroban | BlockChain::Near | BlockChain::MultiversX => {
+                true
+            }
+            BlockChain::SubstratePallet | BlockChain::Solana => false,
         }
     }
 
@@ -47,19 +79,55 @@ impl BlockChain {
         ret
     }
 
+    fn from_dependencies(dependencies: &HashSet<String>) -> Option<Self> {
+        if dependencies.contains("soroban-sdk") {
+            Some(BlockChain::Soroban)
+        } else if dependencies.contains("ink") {
+            Some(BlockChain::Ink)
+        } else if dependencies.contains("frame-system") {
+            Some(BlockChain::SubstratePallet)
+        } else if dependencies.contains("near-sdk") {
+            Some(BlockChain::Near)
+        } else if dependencies.contains("anchor-lang") || dependencies.contains("solana-program") {
+            Some(BlockChain::Solana)
+        } else if dependencies.contains("multiversx-sc") {
+            Some(BlockChain::MultiversX)
+        } else {
+            None
+        }
+    }
+
     #[tracing::instrument(name = "GET BLOCKCHAIN DEPENDENCY", level = "debug", skip_all)]
     pub fn get_blockchain_dependency(metadata: &Metadata) -> Result<Self> {
         let immediate_dependencies = Self::get_immediate_dependencies(metadata);
-        if immediate_dependencies.contains("soroban-sdk") {
-            Ok(BlockChain::Soroban)
-        } else if immediate_dependencies.contains("ink") {
-            Ok(BlockChain::Ink)
-        } else if immediate_dependencies.contains("frame-system") {
-            Ok(BlockChain::SubstratePallet)
-        } else {
+        Self::from_dependencies(&immediate_dependencies).ok_or_else(|| {
             let supported_blockchains = BlockChain::variants().join(", ");
-            Err(anyhow!("Could not find any supported blockchain dependency in the Cargo.toml file.\n   Supported blockchains include:\n   - {}\n",
-                supported_blockchains.replace(", ", "\n   - ")))
+            anyhow!("Could not find any supported blockchain dependency in the Cargo.toml file.\n   Supported blockchains include:\n   - {}\n",
+                supported_blockchains.replace(", ", "\n   - "))
+        })
+    }
+
+    /// Groups the workspace members by the blockchain they target, leaving out the rest.
+    pub fn get_blockchains_by_package(metadata: &Metadata) -> Vec<(Self, Vec<String>)> {
+        let mut ret = Vec::<(Self, Vec<String>)>::new();
+        let members = metadata
+            .workspace_members
+            .iter()
+            .filter_map(|x| metadata.packages.iter().find(|p| p.id == *x));
+        for package in members {
+            let dependencies = package
+                .dependencies
+                .iter()
+                .map(|dep| dep.name.clone())
+                .collect();
+            let Some(blockchain) = Self::from_dependencies(&dependencies) else {
+                continue;
+            };
+            match ret.iter_mut().find(|(x, _)| *x == blockchain) {
+                Some((_, packages)) => packages.push(package.name.clone()),
+                None => ret.push((blockchain, vec![package.name.clone()])),
+            }
         }
+        ret
     }
 }
@@ -0,0 +1,105 @@
+use super::project_info::ProjectInfo;
+use crate::{
+    output::report::{ReportToolchain, SkippedDetector, SuppressedFinding},
+    utils::detectors_info::LintInfo,
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{from_str, Value};
+use std::{collections::HashMap, fs, path::Path};
+use tempfile::TempDir;
+
+// What an analysis hands back to be reported, whether it ran in a nightly child, for one
+// blockchain of `--all-blockchains` or for `analyze`
+#[derive(Serialize, Deserialize, Default)]
+pub(crate) struct FindingsBundle {
+    pub findings: Vec<Value>,
+    pub detectors: HashMap<String, LintInfo>,
+    pub crates: HashMap<String, bool>,
+    pub skipped: Vec<SkippedDetector>,
+    #[serde(default)]
+    pub suppressed: Vec<SuppressedFinding>,
+    #[serde(default)]
+    pub project_info: Option<ProjectInfo>,
+    #[serde(default)]
+    pub available_detectors: usize,
+    #[serde(default)]
+    pub detectors_commit: Option<String>,
+    #[serde(default)]
+    pub toolchains: Vec<ReportToolchain>,
+    // What dylint printed, which VS Code gets as is
+    #[serde(default)]
+    pub raw_output: String,
+    // What an option such as `--toolchain` or `--list` shows instead of analyzing
+    #[serde(default)]
+    pub output: Option<String>,
+    // Cloned repositories and single-file crates, which reports read code snippets from
+    #[serde(skip)]
+    pub workdirs: Vec<TempDir>,
+}
+
+/// What `analyze` produces.
+#[derive(Debug)]
+pub enum Analysis {
+    /// The findings of the analysis
+    Findings(AnalysisResult),
+    /// What an option such as `--toolchain`, `--list` or `--print-schema` shows instead of
+    /// analyzing
+    Output(String),
+}
+
+/// What `analyze` finds in a project.
+#[derive(Debug)]
+pub struct AnalysisResult {
+    pub findings: Vec<Value>,
+    pub crates: HashMap<String, bool>,
+    pub project_info: ProjectInfo,
+    pub detectors_info: HashMap<String, LintInfo>,
+    pub skipped_detectors: Vec<SkippedDetector>,
+    pub suppressed_findings: Vec<SuppressedFinding>,
+    // A cloned repository or single-file crate stays around while `project_info` points at it
+    _workdirs: Vec<TempDir>,
+}
+
+impl FindingsBundle {
+    pub fn with_output(output: String) -> Self {
+        Self {
+            output: Some(output),
+            ..Self::default()
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let file = fs::File::create(path)
+            .with_context(|| format!("Failed to create findings bundle: {}", path.display()))?;
+        serde_json::to_writer(file, self).with_context(|| "Failed to write findings bundle")
+    }
+
+    // Runs that analyze nothing leave the bundle empty
+    pub fn read(path: &Path) -> Result<Option<Self>> {
+        let content = fs::read_to_string(path).with_context(|| "Failed to read findings bundle")?;
+        if content.trim().is_empty() {
+            return Ok(None);
+        }
+        from_str(&content)
+            .map(Some)
+            .with_context(|| "Failed to parse findings bundle")
+    }
+
+    pub fn into_analysis(self) -> Result<Analysis> {
+        if let Some(output) = self.output {
+            return Ok(Analysis::Output(output));
+        }
+        Ok(Analysis::Findings(AnalysisResult {
+            findings: self.findings,
+            crates: self.crates,
+            project_info: self
+                .project_info
+                .with_context(|| "The findings bundle has no project info")?,
+            detectors_info: self.detectors,
+            skipped_detectors: self.skipped,
+            suppressed_findings: self.suppressed,
+            _workdirs: self.workdirs,
+        }))
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{
+    output::raw_report::json_to_string_opt,
+    utils::{command::Command, glob::glob_regex},
+};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+fn normalize(path: &Path) -> PathBuf {
+    dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn git_lines(dir: &Path, args: &[&str]) -> Result<Vec<String>> {
+    let output = Command::new("git").current_dir(dir).args(args).output()?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Returns the files modified against `base` (a commit, branch or tag), staged or not, plus
+/// untracked ones.
+pub fn git_changed_files(dir: &Path, base: &str) -> Result<HashSet<PathBuf>> {
+    let root = git_lines(dir, &["rev-parse", "--show-toplevel"])
+        .with_context(|| "Failed to find the git repository of the project")?
+        .pop()
+        .map(PathBuf::from)
+        .with_context(|| "Failed to find the git repository of the project")?;
+
+    let mut files = git_lines(&root, &["diff", "--name-only", base])
+        .with_context(|| format!("Failed to list the files changed since {}", base))?;
+    files.extend(git_lines(
+        &root,
+        &["ls-files", "--others", "--exclude-standard"],
+    )?);
+
+    Ok(files
+        .iter()
+        .map(|file| normalize(&root.join(file)))
+        .collect())
+}
+
+/// Reads a list of changed files, one per line, relative to `base` unless absolute.
+pub fn read_changed_files(list: &Path, base: &Path) -> Result<HashSet<PathBuf>> {
+    let content = fs::read_to_string(list)
+        .with_context(|| format!("Failed to read changed files list: {}", list.display()))?;
+
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| normalize(&base.join(line)))
+        .collect())
+}
+
+fn finding_file(finding: &Value) -> Option<String> {
+    json_to_string_opt(
+        finding
+            .get("spans")
+            .and_then(|spans| spans.get(0))
+            .and_then(|span| span.get("file_name")),
+    )
+}
+
+/// Keeps the findings whose primary span is in one of the changed files.
+pub fn filter_changed_findings(
+    findings: Vec<Value>,
+    changed: &HashSet<PathBuf>,
+    workspace_root: &Path,
+) -> Vec<Value> {
+    findings
+        .into_iter()
+        .filter(|finding| {
+            finding_file(finding)
+                .is_some_and(|file| changed.contains(&normalize(&workspace_root.join(file))))
+        })
+        .collect()
+}
+
+/// Drops the findings whose workspace-relative path matches any of the `ignored` globs.
+pub fn filter_ignored_paths(
+    findings: Vec<Value>,
+    ignored: &[String],
+    workspace_root: &Path,
+) -> Result<Vec<Value>> {
+    let patterns = ignored
+        .iter()
+        .map(|pattern| glob_regex(pattern))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(findings
+        .into_iter()
+        .filter(|finding| {
+            let Some(file) = finding_file(finding) else {
+                return true;
+            };
+            let path = Path::new(&file);
+            let relative = path.strip_prefix(workspace_root).unwrap_or(path);
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            !patterns.iter().any(|pattern| pattern.is_match(&relative))
+        })
+        .collect())
+}
@@ -0,0 +1,97 @@
+use crate::output::{
+    raw_report::json_to_string_opt,
+    report::{DiffEntry, FindingsDiff},
+};
+use crate::scout::findings::{fingerprint, lint_id};
+use anyhow::{Context, Result};
+use serde_json::{from_str, Value};
+use std::{collections::HashSet, fs, path::Path};
+
+/// Reads a report written with the `raw-json` output format, one finding per line.
+pub fn read_raw_report(path: &Path) -> Result<Vec<Value>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read raw report: {}", path.display()))?;
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            from_str::<Value>(line).with_context(|| {
+                format!(
+                    "Invalid finding at line {} of raw report: {}",
+                    index + 1,
+                    path.display()
+                )
+            })
+        })
+        .collect()
+}
+
+fn diff_entry(finding: &Value) -> DiffEntry {
+    let span = finding.get("spans").and_then(|spans| spans.get(0));
+    let field =
+        |name: &str| json_to_string_opt(span.and_then(|span| span.get(name))).unwrap_or_default();
+
+    DiffEntry {
+        fingerprint: fingerprint(finding),
+        vulnerability_id: lint_id(finding).unwrap_or_default(),
+        location: format!(
+            "{}:{}:{}",
+            field("file_name"),
+            field("line_start"),
+            field("column_start")
+        ),
+        message: json_to_string_opt(finding.get("message")).unwrap_or_default(),
+    }
+}
+
+/// Splits findings into those introduced, fixed and unchanged relative to a previous run.
+pub fn diff_findings(previous: &[Value], current: &[Value], compared_to: &Path) -> FindingsDiff {
+    let previous_fingerprints: HashSet<String> = previous.iter().map(fingerprint).collect();
+    let current_fingerprints: HashSet<String> = current.iter().map(fingerprint).collect();
+
+    let mut diff = FindingsDiff {
+        compared_to: compared_to.display().to_string(),
+        ..FindingsDiff::default()
+    };
+
+    for finding in current.iter() {
+        let entry = diff_entry(finding);
+        if previous_fingerprints.contains(&entry.fingerprint) {
+            diff.unchanged.push(entry);
+        } else {
+            diff.new.push(entry);
+        }
+    }
+
+    for finding in previous.iter() {
+        let entry = diff_entry(finding);
+        if !current_fingerprints.contains(&entry.fingerprint) {
+            diff.fixed.push(entry);
+        }
+    }
+
+    diff
+}
+
+/// Rewrites a raw report without the findings `diff` reports as fixed, keeping every other entry.
+pub fn prune_fixed_findings(path: &Path, previous: &[Value], diff: &FindingsDiff) -> Result<()> {
+    let fixed: HashSet<&str> = diff
+        .fixed
+        .iter()
+        .map(|entry| entry.fingerprint.as_str())
+        .collect();
+
+    let mut content = String::new();
+    for finding in previous
+        .iter()
+        .filter(|finding| !fixed.contains(fingerprint(finding).as_str()))
+    {
+        content.push_str(&finding.to_string());
+        content.push('\n');
+    }
+
+    fs::write(path, content)
+        .with_context(|| format!("Failed to update raw report: {}", path.display()))
+}
@@ -0,0 +1,181 @@
+use crate::{
+    output::{raw_report::json_to_string_opt, severity_map::SeverityMap},
+    utils::detectors_info::{Confidence, LintInfo},
+};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+pub fn lint_id(finding: &Value) -> Option<String> {
+    json_to_string_opt(finding.get("code").and_then(|code| code.get("code")))
+}
+
+fn primary_span(finding: &Value) -> Option<&Value> {
+    finding.get("spans").and_then(|spans| spans.get(0))
+}
+
+// Identifies a finding by its lint id, location and the code it points at, so the same issue
+// reported from different crates (or different runs) compares equal.
+pub fn fingerprint(finding: &Value) -> String {
+    let span = primary_span(finding);
+    let field =
+        |name: &str| json_to_string_opt(span.and_then(|span| span.get(name))).unwrap_or_default();
+    let code = span
+        .and_then(|span| span.get("text"))
+        .and_then(Value::as_array)
+        .map(|lines| {
+            lines
+                .iter()
+                .filter_map(|line| line.get("text").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
+
+    let key = [
+        lint_id(finding).unwrap_or_default(),
+        field("file_name"),
+        field("line_start"),
+        field("column_start"),
+        field("line_end"),
+        field("column_end"),
+        code,
+    ]
+    .join("\u{1f}");
+
+    match orion::hash::digest(key.as_bytes()) {
+        Ok(digest) => digest
+            .as_ref()
+            .iter()
+            .take(16)
+            .map(|byte| format!("{:02x}", byte))
+            .collect(),
+        Err(_) => key,
+    }
+}
+
+// Collapses findings sharing a fingerprint into the first occurrence, recording every crate
+// the finding was reported in under `crates`.
+pub fn dedup_findings(findings: &[Value]) -> Vec<Value> {
+    let mut positions = HashMap::<String, usize>::new();
+    let mut ret = Vec::<Value>::new();
+
+    for finding in findings.iter() {
+        let krate = json_to_string_opt(finding.get("crate"));
+        let key = fingerprint(finding);
+
+        if let Some(&index) = positions.get(&key) {
+            if let (Some(krate), Some(Value::Array(crates))) = (krate, ret[index].get_mut("crates"))
+            {
+                let krate = Value::String(krate);
+                if !crates.contains(&krate) {
+                    crates.push(krate);
+                }
+            }
+            continue;
+        }
+
+        let mut finding = finding.clone();
+        finding["crates"] = Value::Array(krate.into_iter().map(Value::String).collect());
+        positions.insert(key, ret.len());
+        ret.push(finding);
+    }
+
+    ret
+}
+
+// Returns the detectors that ran without emitting any finding, sorted by id.
+pub fn clean_detectors<'a>(
+    detectors: impl IntoIterator<Item = &'a String>,
+    findings: &[Value],
+) -> Vec<String> {
+    let reported: HashSet<String> = findings.iter().filter_map(lint_id).collect();
+    let mut clean: Vec<String> = detectors
+        .into_iter()
+        .filter(|detector| !reported.contains(*detector))
+        .cloned()
+        .collect();
+    clean.sort();
+    clean
+}
+
+// Orders findings by crate, file, line and lint id so reports are stable between runs.
+pub fn sort_findings(findings: &mut [Value]) {
+    findings.sort_by_cached_key(|finding| {
+        let span = primary_span(finding);
+        (
+            json_to_string_opt(finding.get("crate")).unwrap_or_default(),
+            json_to_string_opt(span.and_then(|span| span.get("file_name"))).unwrap_or_default(),
+            span.and_then(|span| span.get("line_start"))
+                .and_then(Value::as_u64)
+                .unwrap_or_default(),
+            span.and_then(|span| span.get("column_start"))
+                .and_then(Value::as_u64)
+                .unwrap_or_default(),
+            lint_id(finding).unwrap_or_default(),
+        )
+    });
+}
+
+/// Drops the findings of detectors less confident than `min`. Findings of unknown detectors are kept.
+pub fn filter_by_confidence(
+    findings: Vec<Value>,
+    min: Confidence,
+    detectors_info: &HashMap<String, LintInfo>,
+) -> Vec<Value> {
+    findings
+        .into_iter()
+        .filter(|finding| {
+            lint_id(finding)
+                .and_then(|id| detectors_info.get(&id))
+                .map_or(true, |info| info.confidence >= min)
+        })
+        .collect()
+}
+
+// Findings of unknown severity sort after all the others.
+fn severity_rank(
+    finding: &Value,
+    detectors_info: &HashMap<String, LintInfo>,
+    severity_map: &SeverityMap,
+) -> usize {
+    lint_id(finding)
+        .and_then(|id| detectors_info.get(&id))
+        .and_then(|info| severity_map.severity(&info.severity))
+        .map_or(usize::MAX, |severity| severity_map.rank(severity))
+}
+
+// Keeps the `max` most severe findings, in their original order, and returns how many were left out.
+pub fn truncate_findings(
+    findings: &[Value],
+    max: usize,
+    detectors_info: &HashMap<String, LintInfo>,
+    severity_map: &SeverityMap,
+) -> (Vec<Value>, usize) {
+    if findings.len() <= max {
+        return (findings.to_vec(), 0);
+    }
+
+    let mut kept: Vec<usize> = (0..findings.len()).collect();
+    kept.sort_by_key(|index| severity_rank(&findings[*index], detectors_info, severity_map));
+    kept.truncate(max);
+    kept.sort_unstable();
+
+    (
+        kept.iter().map(|index| findings[*index].clone()).collect(),
+        findings.len() - max,
+    )
+}
+
+/// Drops the ANSI-colored `rendered` diagnostic, which only the console output needs.
+pub fn strip_rendered(findings: &[Value]) -> Vec<Value> {
+    findings
+        .iter()
+        .cloned()
+        .map(|mut finding| {
+            if let Some(finding) = finding.as_object_mut() {
+                finding.remove("rendered");
+            }
+            finding
+        })
+        .collect()
+}
@@ -0,0 +1,32 @@
+use super::diff::read_raw_report;
+use crate::server::FindingsStream;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// Reads the raw reports given to `--merge`, shaped like the findings the detectors send while
+/// the analysis runs. Duplicates are dropped by fingerprint along with the ones of a regular run.
+pub(crate) fn merge_raw_reports(
+    paths: &[PathBuf],
+    stream: Option<FindingsStream>,
+) -> Result<Vec<String>> {
+    let mut findings = Vec::<Value>::new();
+    for path in paths.iter() {
+        findings.extend(read_raw_report(path)?);
+    }
+    if let Some(mut stream) = stream {
+        for finding in findings.iter() {
+            stream
+                .write(finding)
+                .with_context(|| "Failed to write NDJSON findings")?;
+        }
+    }
+
+    Ok(findings
+        .iter()
+        .map(|finding| {
+            let krate = finding.get("crate").cloned().unwrap_or_default();
+            serde_json::json!({ "crate": krate, "message": finding }).to_string()
+        })
+        .collect())
+}
@@ -1,5 +1,15 @@
+pub mod all_blockchains;
 pub mod blockchain;
+pub mod bundle;
+pub mod changed_files;
+pub mod diff;
+pub mod findings;
+pub mod merge;
 pub mod nightly_runner;
+pub mod post_hook;
 pub mod post_processing;
 pub mod project_info;
+pub mod remote;
+pub mod single_file;
+pub mod suppressions;
 pub mod version_checker;
@@ -1,13 +1,16 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use current_platform::CURRENT_PLATFORM;
 use lazy_static::lazy_static;
 use std::{
     env,
-    path::Path,
+    ffi::OsStr,
+    path::PathBuf,
     process::{Child, Command},
 };
 
-use crate::utils::print::print_error;
+use crate::{startup::Scout, utils::env::SCOUT_NIGHTLY_OPTIONS};
+
+const SCOUT_EXECUTABLE: &str = "cargo-scout-audit";
 
 lazy_static! {
     static ref LIBRARY_PATH_VAR: &'static str = match env::consts::OS {
@@ -18,32 +21,76 @@ lazy_static! {
 }
 
 #[tracing::instrument(name = "RUN SCOUT IN NIGHTLY", skip_all)]
-pub fn run_scout_in_nightly(toolchain: &str) -> Result<Option<Child>> {
-    let current_lib_path = env::var(LIBRARY_PATH_VAR.to_string()).unwrap_or_default();
-    if current_lib_path.contains(toolchain) {
+pub fn run_scout_in_nightly(toolchain: &str, opts: &Scout) -> Result<Option<Child>> {
+    let Some(mut command) = nightly_command(toolchain, opts)? else {
         return Ok(None);
+    };
+
+    if !toolchain_dir(toolchain)?.exists() {
+        bail!(
+            "The {} toolchain required to analyze this project is not installed. Install it with:\n\n     rustup toolchain install {} --component rust-src rustc-dev llvm-tools-preview",
+            toolchain,
+            toolchain
+        );
     }
 
-    let rustup_home = env::var("RUSTUP_HOME").unwrap_or_else(|_| {
-        print_error("Failed to get RUSTUP_HOME, defaulting to '~/.rustup'");
-        "~/.rustup".to_string()
-    });
+    let child = command.spawn().with_context(|| {
+        format!(
+            "Failed to spawn {} with the {} toolchain",
+            command.get_program().to_string_lossy(),
+            toolchain
+        )
+    })?;
+    Ok(Some(child))
+}
 
-    let nightly_lib_path = Path::new(&rustup_home)
+fn toolchain_dir(toolchain: &str) -> Result<PathBuf> {
+    let rustup_home = home::rustup_home().with_context(|| "Failed to find the rustup home")?;
+    Ok(rustup_home
         .join("toolchains")
-        .join(format!("{}-{}", toolchain, CURRENT_PLATFORM))
-        .join("lib");
+        .join(format!("{}-{}", toolchain, CURRENT_PLATFORM)))
+}
 
-    let program_name =
+/// Builds the command that re-runs scout under `toolchain`, or `None` if already running under it.
+///
+/// The options are handed over serialized rather than as command line arguments, so the child
+/// runs with exactly the same options even when scout was not started from the command line.
+/// A program that embeds scout is not scout itself, so its child is the `cargo-scout-audit`
+/// found in `PATH`, which has to be installed for it to analyze projects on another toolchain.
+pub fn nightly_command(toolchain: &str, opts: &Scout) -> Result<Option<Command>> {
+    let current_lib_path = env::var(LIBRARY_PATH_VAR.to_string()).unwrap_or_default();
+    if current_lib_path.contains(toolchain) {
+        return Ok(None);
+    }
+
+    let nightly_lib_path = toolchain_dir(toolchain)?.join("lib");
+
+    let current_exe =
         env::current_exe().with_context(|| "Failed to get current executable path")?;
+    let program_name = if current_exe.file_stem() == Some(OsStr::new(SCOUT_EXECUTABLE)) {
+        current_exe
+    } else {
+        PathBuf::from(SCOUT_EXECUTABLE)
+    };
+
+    let options =
+        serde_json::to_string(opts).with_context(|| "Failed to serialize scout options")?;
 
     let mut command = Command::new(program_name);
     command
-        .args(env::args().skip(1))
-        .env(LIBRARY_PATH_VAR.to_string(), nightly_lib_path);
+        .arg("scout-audit")
+        .env(LIBRARY_PATH_VAR.to_string(), nightly_lib_path)
+        .env(SCOUT_NIGHTLY_OPTIONS, options);
 
-    let child = command
-        .spawn()
-        .with_context(|| "Failed to spawn scout with nightly toolchain")?;
-    Ok(Some(child))
+    Ok(Some(command))
+}
+
+/// Returns the options forwarded by the parent process, if this is a nightly child.
+pub fn forwarded_options() -> Result<Option<Scout>> {
+    let Ok(options) = env::var(SCOUT_NIGHTLY_OPTIONS) else {
+        return Ok(None);
+    };
+    let opts = serde_json::from_str(&options)
+        .with_context(|| "Failed to deserialize the options forwarded to the nightly child")?;
+    Ok(Some(opts))
 }
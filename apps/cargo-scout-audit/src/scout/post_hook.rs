@@ -0,0 +1,33 @@
+use crate::utils::env;
+use anyhow::{ensure, Context, Result};
+use std::{path::PathBuf, process::Command};
+
+/// Runs `hook` through the shell with the generated report paths as its arguments, and the exit
+/// status scout is about to return in `SCOUT_EXIT_STATUS`.
+pub fn run_post_hook(hook: &str, reports: &[PathBuf], exit_status: i32) -> Result<()> {
+    #[cfg(windows)]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(hook);
+        command
+    };
+    #[cfg(not(windows))]
+    let mut command = {
+        let mut command = Command::new("sh");
+        // `sh -c` binds the first argument after the script to `$0`
+        command
+            .arg("-c")
+            .arg(format!("{} \"$@\"", hook))
+            .arg("post-hook");
+        command
+    };
+
+    let status = command
+        .args(reports)
+        .env(env::SCOUT_EXIT_STATUS, exit_status.to_string())
+        .status()
+        .with_context(|| format!("Failed to run post hook `{}`", hook))?;
+    ensure!(status.success(), "Post hook `{}` failed ({})", hook, status);
+
+    Ok(())
+}
@@ -1,17 +1,79 @@
 use anyhow::{bail, Context, Result};
-use cargo_metadata::{camino::Utf8PathBuf, Metadata};
+use cargo_metadata::{camino::Utf8PathBuf, DependencyKind, Metadata};
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    path::{Path, PathBuf},
+};
 
-use crate::output::report::Package;
+use crate::{
+    output::report::{Package, VersionControl},
+    utils::{command::Command, glob::glob_regex},
+};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ProjectInfo {
     pub name: String,
     pub date: String,
     pub workspace_root: PathBuf,
     pub packages: Vec<Package>,
+    pub version_control: Option<VersionControl>,
+}
+
+//In some cases, rustc (or dylint, or clipply, or whoever) has returned the
+//package name where it should be returning the crate name. If you run into
+//problems in the future, try removing the call to this function.
+pub fn normalize_crate_name(s: &str) -> String {
+    let mut ret = String::new();
+    ret.reserve(s.len());
+    for c in s.chars() {
+        ret.push(if c == '-' { '_' } else { c });
+    }
+    ret
+}
+
+// Matches package names, and their crate names with `_` in place of `-`
+pub fn crate_matcher(patterns: &[String]) -> Result<impl Fn(&str) -> bool> {
+    let patterns = patterns
+        .iter()
+        .map(|pattern| glob_regex(pattern))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(move |name: &str| {
+        patterns.is_empty()
+            || patterns.iter().any(|pattern| {
+                pattern.is_match(name) || pattern.is_match(&normalize_crate_name(name))
+            })
+    })
+}
+
+/// Workspace packages that other packages only depend on as dev-dependencies, such as test
+/// helpers. Packages nothing depends on are left out.
+pub fn dev_only_packages(metadata: &Metadata) -> HashSet<String> {
+    let Some(resolve) = &metadata.resolve else {
+        return HashSet::new();
+    };
+
+    // Whether each package is a non-dev dependency of anything
+    let mut dependents = HashMap::new();
+    for dep in resolve.nodes.iter().flat_map(|node| node.deps.iter()) {
+        // Cargo versions before 1.41 leave the kinds out
+        let dev_only = !dep.dep_kinds.is_empty()
+            && dep
+                .dep_kinds
+                .iter()
+                .all(|info| info.kind == DependencyKind::Development);
+        *dependents.entry(&dep.pkg).or_insert(true) &= dev_only;
+    }
+
+    metadata
+        .workspace_packages()
+        .into_iter()
+        .filter(|package| dependents.get(&package.id).copied().unwrap_or(false))
+        .map(|package| package.name.clone())
+        .collect()
 }
 
 lazy_static! {
@@ -20,21 +82,58 @@ lazy_static! {
 
 impl ProjectInfo {
     #[tracing::instrument(name = "GET PROJECT INFO", skip_all)]
-    pub fn get_project_info(metadata: &Metadata) -> Result<Self> {
+    pub fn get_project_info(metadata: &Metadata, date: String) -> Result<Self> {
         let packages = Self::collect_packages(metadata)?;
         let project_name = Self::format_project_name(&metadata.workspace_root)?;
-        let date = chrono::Local::now().format("%Y-%m-%d").to_string();
 
+        let workspace_root = metadata.workspace_root.clone().into_std_path_buf();
         let project_info = ProjectInfo {
             name: project_name,
             date,
-            workspace_root: metadata.workspace_root.clone().into_std_path_buf(),
+            version_control: Self::version_control(&workspace_root),
+            workspace_root,
             packages,
         };
         tracing::trace!(?project_info, "Project info");
         Ok(project_info)
     }
 
+    /// Formats the report date in the local timezone (which honors `TZ`), at `epoch` if given.
+    pub fn format_date(format: &str, epoch: Option<i64>) -> Result<String> {
+        let date = match epoch {
+            Some(epoch) => chrono::DateTime::from_timestamp(epoch, 0)
+                .with_context(|| format!("Invalid date epoch: {}", epoch))?
+                .with_timezone(&chrono::Local),
+            None => chrono::Local::now(),
+        };
+
+        // Formatting an invalid specifier fails here instead of panicking in `to_string`
+        let mut formatted = String::new();
+        write!(formatted, "{}", date.format(format))
+            .map_err(|_| anyhow::anyhow!("Invalid date format: '{}'", format))?;
+        Ok(formatted)
+    }
+
+    // Projects outside a git checkout, or without git installed, have no version control info
+    fn version_control(workspace_root: &Path) -> Option<VersionControl> {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .current_dir(workspace_root)
+                .args(args)
+                .output()
+                .ok()
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+                .filter(|output| !output.is_empty())
+        };
+
+        Some(VersionControl {
+            revision: git(&["rev-parse", "HEAD"])?,
+            repository_uri: git(&["remote", "get-url", "origin"])
+                .map(|remote| repository_uri(&remote)),
+            branch: git(&["rev-parse", "--abbrev-ref", "HEAD"]).filter(|branch| branch != "HEAD"),
+        })
+    }
+
     fn collect_packages(metadata: &Metadata) -> Result<Vec<Package>> {
         let mut packages = Vec::new();
         let workspace_root = &metadata.workspace_root;
@@ -100,3 +199,28 @@ impl ProjectInfo {
             })
     }
 }
+
+// Turns a git remote into a browsable URI, leaving out any credentials CI put in it.
+fn repository_uri(remote: &str) -> String {
+    let uri = match remote.split_once("://") {
+        Some((scheme, rest)) => {
+            let rest = match rest.split_once('/') {
+                Some((authority, path)) => {
+                    let host = authority.rsplit('@').next().unwrap_or(authority);
+                    format!("{}/{}", host, path)
+                }
+                None => rest.to_string(),
+            };
+            format!("{}://{}", scheme, rest)
+        }
+        // scp-like syntax, as in `git@github.com:org/repo.git`
+        None => match remote.split_once(':') {
+            Some((host, path)) => {
+                let host = host.rsplit('@').next().unwrap_or(host);
+                format!("https://{}/{}", host, path)
+            }
+            None => remote.to_string(),
+        },
+    };
+    uri.trim_end_matches(".git").to_string()
+}
@@ -0,0 +1,52 @@
+use crate::utils::command::Command;
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::TempDir;
+
+// The project is named after its directory, so the clone keeps the repository name.
+fn repository_name(url: &str) -> String {
+    let name = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(".git");
+    if name.is_empty() {
+        "repository".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    Command::new("git").current_dir(dir).args(args).output()?;
+    Ok(())
+}
+
+/// Shallow-clones the repository at `url`, checked out at `rev` (a branch, tag or commit) when
+/// given, and returns the temporary directory holding it along with the repository root.
+///
+/// The clone is removed when the returned directory is dropped.
+pub fn clone_repository(url: &str, rev: Option<&str>) -> Result<(TempDir, PathBuf)> {
+    let temp_dir = TempDir::new().with_context(|| "Failed to create temporary directory")?;
+    let root = temp_dir.path().join(repository_name(url));
+    fs::create_dir_all(&root)?;
+
+    let cloned = match rev {
+        // `git clone` can't check out a commit without the full history
+        Some(rev) => git(&root, &["init", "--quiet"])
+            .and_then(|_| git(&root, &["remote", "add", "origin", url]))
+            .and_then(|_| git(&root, &["fetch", "--quiet", "--depth", "1", "origin", rev]))
+            .and_then(|_| git(&root, &["checkout", "--quiet", "FETCH_HEAD"])),
+        None => git(&root, &["clone", "--quiet", "--depth", "1", url, "."]),
+    };
+    cloned.with_context(|| match rev {
+        Some(rev) => format!("Failed to clone {} at {}", url, rev),
+        None => format!("Failed to clone {}", url),
+    })?;
+
+    Ok((temp_dir, root))
+}
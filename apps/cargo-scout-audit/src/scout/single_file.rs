@@ -0,0 +1,133 @@
+use super::blockchain::BlockChain;
+use anyhow::{bail, Context, Result};
+use std::{fs, path::Path};
+use tempfile::TempDir;
+
+// Crates a standalone file may use, the dependency added for each, and the blockchain it implies.
+const KNOWN_SDKS: [(&str, &str, BlockChain); 8] = [
+    (
+        "soroban_sdk",
+        r#"soroban-sdk = "=20.0.0""#,
+        BlockChain::Soroban,
+    ),
+    (
+        "ink",
+        r#"ink = { version = "=5.0.0", default-features = false }"#,
+        BlockChain::Ink,
+    ),
+    (
+        "frame_support",
+        r#"frame-support = { version = "=28.0.0", default-features = false }"#,
+        BlockChain::SubstratePallet,
+    ),
+    (
+        "frame_system",
+        r#"frame-system = { version = "=28.0.0", default-features = false }"#,
+        BlockChain::SubstratePallet,
+    ),
+    ("near_sdk", r#"near-sdk = "=5.1.0""#, BlockChain::Near),
+    (
+        "anchor_lang",
+        r#"anchor-lang = "=0.30.1""#,
+        BlockChain::Solana,
+    ),
+    (
+        "solana_program",
+        r#"solana-program = "=1.18.18""#,
+        BlockChain::Solana,
+    ),
+    (
+        "multiversx_sc",
+        r#"multiversx-sc = "=0.52.3""#,
+        BlockChain::MultiversX,
+    ),
+];
+
+fn uses_crate(source: &str, krate: &str) -> bool {
+    regex::Regex::new(&format!(r"\b{}::", krate))
+        .map(|regex| regex.is_match(source))
+        .unwrap_or(false)
+}
+
+fn sdk_dependencies(source: &str, blockchain: Option<BlockChain>) -> Result<Vec<&'static str>> {
+    let used: Vec<_> = KNOWN_SDKS
+        .iter()
+        .filter(|(krate, _, _)| uses_crate(source, krate))
+        .collect();
+
+    let blockchain = match (blockchain, used.first()) {
+        (Some(blockchain), _) => blockchain,
+        (None, Some((_, _, blockchain))) => *blockchain,
+        (None, None) => bail!(
+            "Could not detect the blockchain the file targets. Use `--blockchain` to set it, one of: {}",
+            BlockChain::variants().join(", ")
+        ),
+    };
+
+    let mut dependencies: Vec<&str> = used
+        .iter()
+        .filter(|(_, _, sdk_blockchain)| *sdk_blockchain == blockchain)
+        .map(|(_, dependency, _)| *dependency)
+        .collect();
+    // The blockchain is detected from its SDK, so the crate needs at least one of them
+    if dependencies.is_empty() {
+        dependencies.extend(
+            KNOWN_SDKS
+                .iter()
+                .find(|(_, _, sdk_blockchain)| *sdk_blockchain == blockchain)
+                .map(|(_, dependency, _)| *dependency),
+        );
+    }
+    Ok(dependencies)
+}
+
+/// Wraps a standalone source file in a temporary crate that depends on the blockchain SDK it uses.
+///
+/// The crate is removed when the returned directory is dropped.
+pub fn synthesize_crate(file: &Path, blockchain: Option<BlockChain>) -> Result<TempDir> {
+    let source = fs::read_to_string(file)
+        .with_context(|| format!("Failed to read file: {}", file.display()))?;
+    let file_name = file
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| name.ends_with(".rs"))
+        .with_context(|| format!("Not a Rust source file: {}", file.display()))?;
+    let mut package_name: String = file_name
+        .trim_end_matches(".rs")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    if !package_name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        package_name.insert_str(0, "snippet-");
+    }
+
+    let dependencies = sdk_dependencies(&source, blockchain)?;
+
+    let temp_dir = TempDir::new().with_context(|| "Failed to create temporary crate")?;
+    let src_dir = temp_dir.path().join("src");
+    fs::create_dir_all(&src_dir)?;
+    // Keeping the original file name makes findings point to it
+    fs::write(src_dir.join(file_name), source)?;
+
+    let manifest = format!(
+        r#"[package]
+name = "{package_name}"
+version = "0.1.0"
+edition = "2021"
+
+[lib]
+path = "src/{file_name}"
+crate-type = ["cdylib", "rlib"]
+
+[dependencies]
+{}
+
+[workspace]
+"#,
+        dependencies.join("\n")
+    );
+    fs::write(temp_dir.path().join("Cargo.toml"), manifest)
+        .with_context(|| "Failed to write the temporary crate manifest")?;
+
+    Ok(temp_dir)
+}
@@ -0,0 +1,82 @@
+use super::findings::lint_id;
+use crate::output::{raw_report::json_to_string_opt, report::SuppressedFinding};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::Value;
+use std::{collections::HashMap, fs, path::Path};
+
+lazy_static! {
+    static ref IGNORE_REGEX: Regex =
+        Regex::new(r"//\s*scout-audit:ignore\s+([\w-]+)(?:\s+(.*))?$").expect("Invalid regex");
+}
+
+// Comments that silence `lint` on `line` (1-based): on the line itself or in the block of
+// comments right above it. Returns the reason given, which may be empty.
+fn ignore_reason(lines: &[String], line: usize, lint: &str) -> Option<String> {
+    let matching = |text: &str| {
+        IGNORE_REGEX.captures(text).and_then(|captures| {
+            (captures[1].replace('-', "_") == lint).then(|| {
+                captures
+                    .get(2)
+                    .map_or(String::new(), |reason| reason.as_str().trim().to_string())
+            })
+        })
+    };
+
+    let index = line.checked_sub(1)?;
+    if let Some(reason) = lines.get(index).and_then(|text| matching(text)) {
+        return Some(reason);
+    }
+    lines[..index.min(lines.len())]
+        .iter()
+        .rev()
+        .take_while(|text| text.trim_start().starts_with("//"))
+        .find_map(|text| matching(text))
+}
+
+/// Splits out the findings silenced with a `// scout-audit:ignore <lint-id> [reason]` comment.
+pub fn split_suppressed(
+    findings: Vec<Value>,
+    workspace_root: &Path,
+) -> (Vec<Value>, Vec<SuppressedFinding>) {
+    let mut sources = HashMap::<String, Vec<String>>::new();
+    let mut kept = Vec::new();
+    let mut suppressed = Vec::new();
+
+    for finding in findings {
+        let span = finding.get("spans").and_then(|spans| spans.get(0));
+        let file = json_to_string_opt(span.and_then(|span| span.get("file_name")));
+        let line = span
+            .and_then(|span| span.get("line_start"))
+            .and_then(Value::as_u64);
+        let (Some(file), Some(line), Some(lint)) = (file, line, lint_id(&finding)) else {
+            kept.push(finding);
+            continue;
+        };
+
+        let lines = sources.entry(file.clone()).or_insert_with(|| {
+            fs::read_to_string(workspace_root.join(&file))
+                .map(|source| source.lines().map(String::from).collect())
+                .unwrap_or_default()
+        });
+
+        let Some(reason) = ignore_reason(lines, line as usize, &lint) else {
+            kept.push(finding);
+            continue;
+        };
+        // The same finding is reported once per crate that compiles the file
+        let location = format!("{}:{}", file, line);
+        if !suppressed.iter().any(|entry: &SuppressedFinding| {
+            entry.vulnerability_id == lint && entry.location == location
+        }) {
+            suppressed.push(SuppressedFinding {
+                vulnerability_id: lint,
+                location,
+                message: json_to_string_opt(finding.get("message")).unwrap_or_default(),
+                reason: Some(reason).filter(|reason| !reason.is_empty()),
+            });
+        }
+    }
+
+    (kept, suppressed)
+}
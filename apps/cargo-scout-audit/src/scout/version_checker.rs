@@ -1,29 +1,64 @@
+use crate::{output::utils::write_to_file, utils::paths};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use reqwest::blocking::Client;
 use semver::Version;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::env;
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 const CRATE_NAME: &str = env!("CARGO_PKG_NAME");
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Serialize, Deserialize)]
+struct VersionCache {
+    checked_at: u64,
+    latest_version: String,
+}
 
-#[derive(Default)]
 pub struct VersionChecker {
     client: Client,
+    interval: Duration,
+}
+
+impl Default for VersionChecker {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl VersionChecker {
     pub fn new() -> Self {
         VersionChecker {
             client: Client::new(),
+            interval: DEFAULT_INTERVAL,
         }
     }
 
-    pub fn check_for_updates(&self) -> Result<()> {
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    // Checks crates.io at most once per interval unless `force` is set, reusing the cached
+    // latest version otherwise.
+    pub fn check_for_updates(&self, force: bool) -> Result<()> {
         let current_version =
             Version::parse(CURRENT_VERSION).with_context(|| "Failed to parse current version")?;
-        let latest_version = self.get_latest_version()?;
+        let cached = if force { None } else { self.read_cache() };
+        let latest_version = match cached {
+            Some(version) => version,
+            None => {
+                let version = self.get_latest_version()?;
+                self.write_cache(&version);
+                version
+            }
+        };
 
         if latest_version > current_version {
             self.print_update_warning(&current_version, &latest_version);
@@ -32,6 +67,40 @@ impl VersionChecker {
         Ok(())
     }
 
+    fn cache_path() -> Option<PathBuf> {
+        paths::scout_home().map(|home| home.join("version-check.json"))
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default()
+    }
+
+    fn read_cache(&self) -> Option<Version> {
+        let content = fs::read_to_string(Self::cache_path()?).ok()?;
+        let cache: VersionCache = serde_json::from_str(&content).ok()?;
+        if Self::now().saturating_sub(cache.checked_at) >= self.interval.as_secs() {
+            return None;
+        }
+        Version::parse(&cache.latest_version).ok()
+    }
+
+    // A cache that cannot be written only costs another request next time.
+    fn write_cache(&self, latest_version: &Version) {
+        let Some(path) = Self::cache_path() else {
+            return;
+        };
+        let cache = VersionCache {
+            checked_at: Self::now(),
+            latest_version: latest_version.to_string(),
+        };
+        if let Ok(content) = serde_json::to_string(&cache) {
+            let _ = write_to_file(&path, content.as_bytes());
+        }
+    }
+
     fn get_latest_version(&self) -> Result<Version> {
         let url = format!("https://crates.io/api/v1/crates/{}", CRATE_NAME);
         let response = self
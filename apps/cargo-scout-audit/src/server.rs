@@ -1,33 +1,214 @@
-use axum::{http::StatusCode, routing::post, Router};
+use crate::{
+    output::utils::write_to_file,
+    scout::findings::lint_id,
+    utils::{env, print::print_error},
+};
+use axum::{
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::{
+    collections::BTreeMap,
+    io::Write,
+    net::{IpAddr, Ipv4Addr},
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
     time::Duration,
 };
 
-fn port_is_available_on_localhost(port: u16) -> bool {
-    std::net::TcpListener::bind(("127.0.0.1", port)).is_ok()
+// The server sets the address and port it listens on in `SCOUT_SERVER_ADDRESS` and
+// `SCOUT_PORT_NUMBER`, for the detectors and editor extensions to find it.
+
+/// Where the findings server listens. A port of 0 lets the OS pick a free one, and no port the
+/// first free one from 49152.
+#[derive(Debug, Clone)]
+pub(crate) struct ServerConfig {
+    pub address: IpAddr,
+    pub port: Option<u16>,
+    pub port_file: Option<PathBuf>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            port: None,
+            port_file: None,
+        }
+    }
+}
+
+/// Contents of the `--server-port-file`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServerAddress {
+    pub address: IpAddr,
+    pub port: u16,
+}
+
+/// Whether a `POST /vuln` carries a rustc diagnostic, or output that couldn't be parsed as one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingKind {
+    #[default]
+    Diagnostic,
+    Message,
+}
+
+/// Body of a `POST /vuln`: a finding as rustc emits it, or the text of an unstructured message,
+/// with the crate it was found in. Only diagnostics are findings; messages and bodies that don't
+/// parse are printed to stderr.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindingRequest {
+    // Detectors built before it existed only send diagnostics
+    #[serde(default)]
+    pub kind: FindingKind,
+    #[serde(rename = "crate")]
+    pub krate: String,
+    pub message: Value,
+    // Milliseconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+}
+
+/// Response to a `POST /vuln`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FindingResponse {
+    pub accepted: bool,
+    pub received: usize,
+}
+
+/// Response to a `GET /health`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthResponse {
+    pub status: String,
+    pub version: String,
+}
+
+/// Response to a `GET /summary`, with the findings received so far.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SummaryResponse {
+    pub total_findings: usize,
+    pub by_crate: BTreeMap<String, usize>,
+    pub by_detector: BTreeMap<String, usize>,
+}
+
+// Destination findings are streamed to, one JSON object per line, as detectors report them.
+pub(crate) type FindingsWriter = Box<dyn Write + Send>;
+
+/// Writes the findings that make it into the report as they arrive.
+pub(crate) struct FindingsStream {
+    writer: FindingsWriter,
+    keep: Box<dyn FnMut(&Value) -> bool + Send>,
+}
+
+impl FindingsStream {
+    pub fn new(writer: FindingsWriter, keep: impl FnMut(&Value) -> bool + Send + 'static) -> Self {
+        FindingsStream {
+            writer,
+            keep: Box::new(keep),
+        }
+    }
+
+    pub fn write(&mut self, finding: &Value) -> std::io::Result<()> {
+        if !(self.keep)(finding) {
+            return Ok(());
+        }
+        writeln!(self.writer, "{}", finding)?;
+        self.writer.flush()
+    }
+}
+
+fn port_is_available(address: IpAddr, port: u16) -> bool {
+    std::net::TcpListener::bind((address, port)).is_ok()
 }
 
-fn find_available_port(first: Option<u16>) -> Option<u16> {
-    (first.unwrap_or(49152)..65535).find(|port| port_is_available_on_localhost(*port))
+fn find_available_port(address: IpAddr, first: Option<u16>) -> Option<u16> {
+    (first.unwrap_or(49152)..65535).find(|port| port_is_available(address, *port))
+}
+
+fn write_port_file(path: &Path, address: &ServerAddress) -> anyhow::Result<()> {
+    let json = serde_json::to_string_pretty(address)?;
+    write_to_file(&path.to_path_buf(), json.as_bytes())?;
+    Ok(())
 }
 
 pub(crate) struct AppState {
     pub findings: Mutex<Vec<String>>,
     pub running_state: Mutex<u32>,
+    pub stream: Option<Mutex<FindingsStream>>,
 }
 
 impl AppState {
-    pub fn new() -> AppState {
+    pub fn new(stream: Option<FindingsStream>) -> AppState {
         AppState {
             findings: Mutex::new(Vec::<String>::new()),
             running_state: Mutex::new(0),
+            stream: stream.map(Mutex::new),
+        }
+    }
+}
+
+fn stream_finding(stream: &Mutex<FindingsStream>, finding: &FindingRequest) {
+    let mut message = finding.message.clone();
+    message["crate"] = Value::String(finding.krate.clone());
+
+    let _ = stream.lock().unwrap().write(&message);
+}
+
+async fn vuln_handler(state: Arc<AppState>, body: String) -> Json<FindingResponse> {
+    let finding = serde_json::from_str::<FindingRequest>(&body)
+        .ok()
+        .filter(|finding| finding.kind == FindingKind::Diagnostic);
+    let Some(finding) = finding else {
+        // Not a finding, but it must not be lost either
+        let request = serde_json::from_str::<FindingRequest>(&body).ok();
+        match request
+            .as_ref()
+            .and_then(|request| request.message.as_str())
+        {
+            Some(text) => eprintln!("{text}"),
+            None => eprintln!("{body}"),
         }
+        return Json(FindingResponse {
+            accepted: false,
+            received: state.findings.lock().unwrap().len(),
+        });
+    };
+
+    if let Some(stream) = &state.stream {
+        stream_finding(stream, &finding);
     }
+    let mut findings = state.findings.lock().unwrap();
+    findings.push(body);
+    Json(FindingResponse {
+        accepted: true,
+        received: findings.len(),
+    })
 }
 
-async fn vuln_handler(state: Arc<AppState>, body: String) {
-    state.findings.lock().unwrap().push(body);
+async fn health_handler() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+async fn summary_handler(state: Arc<AppState>) -> Json<SummaryResponse> {
+    let mut summary = SummaryResponse::default();
+    for body in state.findings.lock().unwrap().iter() {
+        let Ok(finding) = serde_json::from_str::<FindingRequest>(body) else {
+            continue;
+        };
+        summary.total_findings += 1;
+        *summary.by_crate.entry(finding.krate).or_default() += 1;
+        if let Some(id) = lint_id(&finding.message) {
+            *summary.by_detector.entry(id).or_default() += 1;
+        }
+    }
+    Json(summary)
 }
 
 async fn print_handler(body: String) {
@@ -55,15 +236,19 @@ async fn graceful_shutdown(state: Arc<AppState>) {
 }
 
 #[tokio::main]
-async fn server_thread(state: Arc<AppState>) {
-    let mut first: Option<u16> = None;
+async fn server_thread(state: Arc<AppState>, config: ServerConfig) {
+    // Lets `start_server` go on without a server
+    let give_up = || *state.running_state.lock().unwrap() = 2;
+    let mut first = config.port;
     loop {
-        let port = find_available_port(first);
-        if port.is_none() {
-            return;
-        }
-        let port = port.unwrap();
-        std::env::set_var("SCOUT_PORT_NUMBER", port.to_string());
+        let port = if first == Some(0) {
+            0
+        } else {
+            match find_available_port(config.address, first) {
+                Some(port) => port,
+                None => return give_up(),
+            }
+        };
         // build our application with a route
         let app = Router::new()
             .route(
@@ -73,18 +258,49 @@ async fn server_thread(state: Arc<AppState>) {
                     move |body| vuln_handler(state2, body)
                 }),
             )
+            .route("/health", get(health_handler))
+            .route(
+                "/summary",
+                get({
+                    let state2 = state.clone();
+                    move || summary_handler(state2)
+                }),
+            )
             .route("/print", post(print_handler))
             .route("/vuln2", post(test_handler2));
 
-        let address = "127.0.0.1:".to_string() + port.to_string().as_str();
-
         // run it
-        let result = tokio::net::TcpListener::bind(address).await;
+        let result = tokio::net::TcpListener::bind((config.address, port)).await;
         if result.is_err() {
+            if port == 0 {
+                return give_up();
+            }
             first = Some(port + 1);
             continue;
         }
         let listener = result.unwrap();
+        let port = listener.local_addr().map_or(port, |address| address.port());
+
+        // Clients reach a server listening on every interface through the loopback one
+        let address = ServerAddress {
+            address: if config.address.is_unspecified() {
+                IpAddr::V4(Ipv4Addr::LOCALHOST)
+            } else {
+                config.address
+            },
+            port,
+        };
+        std::env::set_var(env::SCOUT_SERVER_ADDRESS, address.address.to_string());
+        std::env::set_var(env::SCOUT_PORT_NUMBER, port.to_string());
+        if let Some(path) = &config.port_file {
+            if let Err(e) = write_port_file(path, &address) {
+                print_error(&format!(
+                    "Failed to write the server port file {}: {}",
+                    path.display(),
+                    e
+                ));
+            }
+        }
 
         let future =
             axum::serve(listener, app).with_graceful_shutdown(graceful_shutdown(state.clone()));
@@ -96,9 +312,10 @@ async fn server_thread(state: Arc<AppState>) {
     }
 }
 
-fn start_server(state: Arc<AppState>) -> std::thread::JoinHandle<()> {
+fn start_server(state: Arc<AppState>, config: &ServerConfig) -> std::thread::JoinHandle<()> {
     let state2 = state.clone();
-    let ret = std::thread::spawn(|| server_thread(state2));
+    let config = config.clone();
+    let ret = std::thread::spawn(|| server_thread(state2, config));
     let not_running = || *state.running_state.lock().unwrap() < 1;
     //let not_running = || true;
     while not_running() {
@@ -109,9 +326,11 @@ fn start_server(state: Arc<AppState>) -> std::thread::JoinHandle<()> {
 
 pub(crate) fn capture_output<T, E, F: FnOnce() -> Result<T, E>>(
     cb: F,
+    stream: Option<FindingsStream>,
+    config: &ServerConfig,
 ) -> Result<(Vec<String>, T), E> {
-    let state = Arc::new(AppState::new());
-    let handle = start_server(state.clone());
+    let state = Arc::new(AppState::new(stream));
+    let handle = start_server(state.clone(), config);
 
     let result = cb();
 
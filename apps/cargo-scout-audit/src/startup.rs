@@ -3,18 +3,57 @@ use crate::{
         builder::DetectorBuilder,
         configuration::{get_local_detectors_configuration, get_remote_detectors_configuration},
     },
-    output::raw_report::{json_to_string, json_to_string_opt, RawReport},
+    output::{
+        raw_report::{json_to_string, json_to_string_opt, RawReport},
+        report::{
+            DetectorCoverage, Report, ReportMetadata, ReportStatus, ReportToolchain,
+            SkippedDetector, SuppressedFinding,
+        },
+        schema::get_schema,
+        severity_map::SeverityMap,
+        status::RunStatus,
+        utils::expand_env_vars,
+    },
     scout::{
-        blockchain::BlockChain, nightly_runner::run_scout_in_nightly,
-        post_processing::PostProcessing, project_info::ProjectInfo,
+        all_blockchains::analyze_all_blockchains,
+        blockchain::BlockChain,
+        bundle::{Analysis, FindingsBundle},
+        changed_files::{
+            filter_changed_findings, filter_ignored_paths, git_changed_files, read_changed_files,
+        },
+        diff::{diff_findings, prune_fixed_findings, read_raw_report},
+        findings::{
+            clean_detectors, dedup_findings, filter_by_confidence, fingerprint, lint_id,
+            sort_findings, strip_rendered, truncate_findings,
+        },
+        merge::merge_raw_reports,
+        nightly_runner::{forwarded_options, run_scout_in_nightly},
+        post_hook::run_post_hook,
+        post_processing::PostProcessing,
+        project_info::{crate_matcher, dev_only_packages, normalize_crate_name, ProjectInfo},
+        remote::clone_repository,
+        single_file::synthesize_crate,
+        suppressions::split_suppressed,
         version_checker::VersionChecker,
     },
-    server::capture_output,
+    server::{capture_output, FindingsStream, ServerConfig},
     utils::{
-        config::{open_config_and_sync_detectors, profile_enabled_detectors},
-        detectors::{get_excluded_detectors, get_filtered_detectors, list_detectors},
-        detectors_info::{get_detectors_info, CustomLint, LintInfo},
-        print::{print_error, print_warning},
+        cargo::LockFlags,
+        config::{
+            generate_profile, get_profiles, open_config_and_sync_detectors,
+            profile_enabled_detectors, profile_severity_overrides,
+        },
+        detectors::{
+            explain_detector, get_excluded_detectors, get_filtered_detectors, list_categories,
+            list_detectors, list_profiles, self_test_detectors,
+        },
+        detectors_info::{
+            get_cached_detectors_info, get_detectors_info, Confidence, CustomLint, LintInfo,
+        },
+        env::{self, ScopedEnv},
+        glob::glob_regex,
+        print::{print_error, print_warning, PrintSettings},
+        progress,
     },
 };
 use anyhow::{anyhow, bail, Context, Ok, Result};
@@ -22,12 +61,16 @@ use cargo::{core::Verbosity, GlobalContext};
 use cargo_metadata::{Metadata, MetadataCommand};
 use clap::{Parser, Subcommand, ValueEnum};
 use dylint::opts::{Check, Dylint, LibrarySelection, Operation};
+use serde::{Deserialize, Serialize};
 use serde_json::{from_str, to_string_pretty, Value};
 use std::{
     collections::{HashMap, HashSet},
     fs,
-    io::Write,
-    path::PathBuf,
+    io::{BufRead, BufReader, IsTerminal, Write},
+    net::IpAddr,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
 };
 use tempfile::NamedTempFile;
 use terminal_color_builder::OutputFormatter;
@@ -44,7 +87,23 @@ pub enum CargoSubCommand {
     ScoutAudit(Scout),
 }
 
-#[derive(Debug, Default, Clone, ValueEnum, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, ValueEnum, PartialEq, Serialize, Deserialize)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Default, Clone, Copy, ValueEnum, PartialEq, Serialize, Deserialize)]
+pub enum ConsoleFormat {
+    #[default]
+    Pretty,
+    // One `severity lint-id file:line message` line per finding
+    Short,
+}
+
+#[derive(Debug, Default, Clone, ValueEnum, PartialEq, Serialize, Deserialize)]
 pub enum OutputFormat {
     #[default]
     Html,
@@ -56,20 +115,113 @@ pub enum OutputFormat {
     MarkdownGithub,
     Sarif,
     Pdf,
+    #[clap(name = "codeclimate")]
+    CodeClimate,
+    Ndjson,
+    // Findings keyed by file, for editor integrations
+    #[clap(name = "json-by-file")]
+    JsonByFile,
+    Toml,
 }
 
-#[derive(Clone, Debug, Default, Parser)]
+impl OutputFormat {
+    // File the report is written to when no `--output-path` is given
+    pub fn default_path(&self) -> PathBuf {
+        PathBuf::from(match self {
+            OutputFormat::Html => "report.html",
+            OutputFormat::Json => "report.json",
+            OutputFormat::RawJson => "raw-report.json",
+            OutputFormat::Markdown | OutputFormat::MarkdownGithub => "report.md",
+            OutputFormat::Sarif => "report.sarif",
+            OutputFormat::Pdf => "report.pdf",
+            OutputFormat::CodeClimate => "codeclimate.json",
+            OutputFormat::Ndjson => "findings.ndjson",
+            OutputFormat::JsonByFile => "findings-by-file.json",
+            OutputFormat::Toml => "report.toml",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, Parser, Serialize, Deserialize)]
 #[command(author, version, about, long_about = None)]
 pub struct Scout {
     #[clap(short, long, value_name = "path", help = "Path to Cargo.toml.")]
     pub manifest_path: Option<PathBuf>,
 
+    #[clap(
+        long,
+        value_name = "path",
+        help = "Read pre-computed `cargo metadata` JSON from the given file (or `-` for stdin) instead of running cargo metadata."
+    )]
+    pub metadata_file: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "path",
+        help = "Analyze a single Rust file outside a cargo project, wrapped in a temporary crate."
+    )]
+    pub file: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "url",
+        help = "Analyze a remote git repository, shallow-cloned into a temporary directory. `--manifest-path` is then relative to the repository."
+    )]
+    pub git: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "rev",
+        requires = "git",
+        help = "Branch, tag or commit of the --git repository to analyze."
+    )]
+    pub rev: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "name",
+        help = "Analyze for the given blockchain instead of detecting it from the dependencies."
+    )]
+    pub blockchain: Option<String>,
+
+    #[clap(
+        long,
+        conflicts_with = "blockchain",
+        help = "Analyze every blockchain found in the workspace with its own detectors and toolchain, and merge the findings in one report."
+    )]
+    pub all_blockchains: bool,
+
+    #[clap(
+        long,
+        value_name = "ip",
+        help = "Address the findings server listens on. Defaults to SCOUT_BIND_ADDRESS, or 127.0.0.1."
+    )]
+    pub server_address: Option<IpAddr>,
+
+    #[clap(
+        long,
+        value_name = "port",
+        help = "Port the findings server listens on, or 0 to let the OS pick a free one. Defaults to SCOUT_SERVER_PORT, or the first free port from 49152."
+    )]
+    pub server_port: Option<u16>,
+
+    #[clap(
+        long,
+        value_name = "path",
+        help = "Write the address and port the findings server listens on to this JSON file."
+    )]
+    pub server_port_file: Option<PathBuf>,
+
+    // Set on the runs in a nightly child process, which hand their findings back through it
+    #[clap(skip)]
+    pub findings_bundle: Option<PathBuf>,
+
     // Exlude detectors
     #[clap(
         short,
         long,
         value_name = "detector/s",
-        help = "Exclude the given detectors, separated by commas."
+        help = "Exclude the given detectors, separated by commas. Glob patterns such as `unsafe-*` are supported."
     )]
     pub exclude: Option<String>,
 
@@ -78,10 +230,16 @@ pub struct Scout {
         short,
         long,
         value_name = "detector/s",
-        help = "Filter by the given detectors, separated by commas."
+        help = "Filter by the given detectors, separated by commas. Glob patterns such as `unsafe-*` are supported."
     )]
     pub filter: Option<String>,
 
+    #[clap(
+        long,
+        help = "Fail instead of warning when a --filter or --exclude glob matches no detector, or --exclude names a detector that does not exist. Unknown --filter names always fail."
+    )]
+    pub strict: bool,
+
     // Select profiles in configuration
     #[clap(
         short,
@@ -95,6 +253,61 @@ pub struct Scout {
     #[clap(short, long, help = "List all the available detectors")]
     pub list_detectors: bool,
 
+    #[clap(
+        long,
+        help = "List the profiles defined in the configuration file and their detectors."
+    )]
+    pub list_profiles: bool,
+
+    #[clap(
+        long,
+        value_name = "profile",
+        help = "Add a profile listing every detector with an `enabled` field to the configuration file, then exit."
+    )]
+    pub config_profile_generate: Option<String>,
+
+    #[clap(
+        short,
+        long,
+        value_name = "n",
+        help = "Number of parallel jobs used to build detectors and check the project."
+    )]
+    pub jobs: Option<u32>,
+
+    // `-p` is already taken by `--profile`
+    #[clap(
+        long,
+        value_name = "name",
+        help = "Only analyze the given workspace package. Can be repeated."
+    )]
+    pub package: Vec<String>,
+
+    #[clap(
+        long,
+        value_name = "glob",
+        help = "Only analyze and report the workspace crates whose name matches the glob, on top of --package. Can be repeated."
+    )]
+    pub only_crates: Vec<String>,
+
+    #[clap(
+        long,
+        help = "Also report the findings of workspace packages that are only used as dev-dependencies, such as test helpers."
+    )]
+    pub include_dev_deps: bool,
+
+    // Target selection, as in `cargo check`
+    #[clap(long, help = "Only analyze the library of the packages.")]
+    pub lib: bool,
+
+    #[clap(long, help = "Only analyze the binaries of the packages.")]
+    pub bins: bool,
+
+    #[clap(long, help = "Only analyze the tests of the packages.")]
+    pub tests: bool,
+
+    #[clap(long, help = "Only analyze the examples of the packages.")]
+    pub examples: bool,
+
     #[clap(last = true, help = "Arguments for `cargo check`.")]
     pub args: Vec<String>,
 
@@ -107,12 +320,198 @@ pub struct Scout {
     )]
     pub output_format: Vec<OutputFormat>,
 
-    #[clap(long, value_name = "path", help = "Path to the output file.")]
+    #[clap(
+        long,
+        value_name = "path",
+        help = "Path to the output file. With `ndjson`, `-` streams findings to stdout. Streamed findings go through the same filters and deduplication as the reports, but may include findings of crates that later fail to compile."
+    )]
     pub output_path: Option<PathBuf>,
 
+    #[clap(
+        long,
+        value_name = "strftime",
+        help = "Format of the report date, in strftime syntax (default: %Y-%m-%d). The date is local to the `TZ` timezone."
+    )]
+    pub date_format: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "seconds",
+        help = "Date the report at the given Unix timestamp instead of now, for reproducible reports."
+    )]
+    pub date_epoch: Option<i64>,
+
+    #[clap(
+        long,
+        value_name = "n",
+        help = "Only report the n most severe findings, noting how many were omitted. The raw-json, codeclimate and json-by-file outputs have no room for the note, so they keep every finding."
+    )]
+    pub max_findings: Option<usize>,
+
+    #[clap(
+        long,
+        requires = "max_findings",
+        help = "Write every finding to the json and toml reports, even with --max-findings."
+    )]
+    pub no_truncate_json: bool,
+
+    #[clap(
+        long,
+        help = "Embed the scout version, toolchain and full detectors catalog in the json report."
+    )]
+    pub embed_metadata: bool,
+
+    #[clap(
+        long,
+        help = "Leave the ANSI-colored `rendered` diagnostic out of the raw-json report. The json report never includes it."
+    )]
+    pub strip_rendered: bool,
+
+    #[clap(
+        long,
+        help = "Only output the findings summary: the console table and the per-category counts of the markdown and json reports."
+    )]
+    pub summary_only: bool,
+
+    #[clap(
+        long,
+        value_name = "title",
+        help = "Title of the HTML, PDF and markdown reports. Defaults to the project name."
+    )]
+    pub report_title: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "author",
+        help = "Author or company shown in the headers of the HTML, PDF and markdown reports."
+    )]
+    pub report_author: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "path",
+        help = "JSON file mapping scout severities to SARIF levels and CodeClimate severities, custom severity labels to scout severities, and the order of scout severities from the most to the least severe."
+    )]
+    pub severity_map: Option<PathBuf>,
+
+    #[clap(
+        long,
+        help = "Open the generated reports with the default application for their format."
+    )]
+    pub open: bool,
+
+    #[clap(
+        long,
+        value_name = "path",
+        help = "Compare findings against a previous raw-json report and fail only on new findings."
+    )]
+    pub compare_to: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "path",
+        num_args = 1..,
+        help = "Generate the reports from these raw-json reports, such as the ones of sharded CI jobs, instead of analyzing the project. Findings found by several of them are reported once."
+    )]
+    pub merge: Vec<PathBuf>,
+
+    #[clap(
+        long,
+        requires = "compare_to",
+        help = "Remove fixed findings from the --compare-to report. New findings are never added to it."
+    )]
+    pub baseline_update_on_fix: bool,
+
+    #[clap(
+        long,
+        value_name = "detector/s",
+        value_delimiter = ',',
+        help = "Fail the run when any of the given detectors reports a finding, whatever its severity."
+    )]
+    pub deny: Vec<String>,
+
+    #[clap(
+        long,
+        value_name = "detector/s",
+        value_delimiter = ',',
+        help = "Keep reporting the given detectors, but never fail the run because of them."
+    )]
+    pub allow: Vec<String>,
+
+    #[clap(
+        long,
+        help = "Only report findings in files changed according to git, or listed in --changed-files."
+    )]
+    pub changed_only: bool,
+
+    #[clap(
+        long,
+        value_name = "glob",
+        help = "Ignore findings in files matching this glob, relative to the workspace root. Can be repeated."
+    )]
+    pub ignore_path: Vec<String>,
+
+    #[clap(
+        long,
+        value_enum,
+        value_name = "level",
+        help = "Only report findings of detectors with at least this confidence."
+    )]
+    pub min_confidence: Option<Confidence>,
+
+    #[clap(
+        long,
+        value_name = "path",
+        requires = "changed_only",
+        help = "File listing the changed files, one per line, to use instead of git."
+    )]
+    pub changed_files: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "git-ref",
+        conflicts_with = "changed_files",
+        help = "Only report findings in files changed since this git ref, and fail the run if there are any."
+    )]
+    pub since: Option<String>,
+
+    #[clap(
+        long,
+        help = "Fail the run and print the compilation errors when a crate fails to compile, instead of reporting the partial results. The analysis itself still runs to the end."
+    )]
+    pub fail_fast: bool,
+
+    #[clap(
+        long,
+        value_name = "path",
+        help = "Write a JSON summary of the run (findings by severity, crates and exit status) to this file."
+    )]
+    pub status_file: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "command",
+        help = "Command to run after the reports are generated. It gets the report paths as arguments and scout's exit status in SCOUT_EXIT_STATUS."
+    )]
+    pub post_hook: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "path",
+        help = "Directory to load custom console, markdown and HTML templates from."
+    )]
+    pub template_dir: Option<PathBuf>,
+
     #[clap(long, value_name = "path", help = "Path to detectors workspace.")]
     pub local_detectors: Option<PathBuf>,
 
+    #[clap(
+        long,
+        value_name = "git-ref",
+        help = "Pin the remote detectors to a commit SHA or tag for reproducible audits."
+    )]
+    pub detectors_ref: Option<String>,
+
     #[clap(
         long,
         help = "Force fallback to secondary detectors branch.",
@@ -128,6 +527,35 @@ pub struct Scout {
     )]
     pub verbose: bool,
 
+    #[clap(
+        long,
+        help = "Print the elapsed time, peak memory and number of crates and detectors at the end of the run. Implied by --verbose."
+    )]
+    pub stats: bool,
+
+    #[clap(
+        short,
+        long,
+        help = "Only write the requested report, without console output, progress or warnings."
+    )]
+    pub quiet: bool,
+
+    #[clap(
+        long,
+        value_name = "when",
+        default_value = "auto",
+        help = "Colorize console output. `auto` only colors when stdout is a terminal."
+    )]
+    pub color: ColorChoice,
+
+    #[clap(
+        long,
+        value_name = "format",
+        default_value = "pretty",
+        help = "How findings are printed to the console. `short` prints one line per finding."
+    )]
+    pub format: ConsoleFormat,
+
     #[clap(
         name = "toolchain",
         long,
@@ -144,6 +572,52 @@ pub struct Scout {
     )]
     pub detectors_metadata: bool,
 
+    #[clap(
+        long,
+        value_name = "detector",
+        help = "Explain what the given detector looks for and exit."
+    )]
+    pub explain: Option<String>,
+
+    #[clap(
+        long,
+        help = "List the vulnerability categories covered by the selected detectors and exit."
+    )]
+    pub list_categories: bool,
+
+    #[clap(
+        long,
+        help = "Build and load every detector, check that each exports a well-formed lint_info, and exit without analyzing."
+    )]
+    pub self_test: bool,
+
+    #[clap(
+        long,
+        help = "Append the id of the detector that produced each finding to the console output."
+    )]
+    pub show_detector_id: bool,
+
+    #[clap(
+        long,
+        value_name = "format",
+        help = "Print the JSON Schema of the given report format (json, raw-json or sarif) and exit."
+    )]
+    pub print_schema: Option<OutputFormat>,
+
+    #[clap(
+        long,
+        help = "Check for a newer Scout version now, ignoring the cached result."
+    )]
+    pub check_updates: bool,
+
+    #[clap(
+        long,
+        value_name = "hours",
+        default_value_t = 24,
+        help = "Hours to wait between checks for a newer Scout version."
+    )]
+    pub update_check_interval: u64,
+
     #[clap(
         name = "debug",
         long,
@@ -151,16 +625,61 @@ pub struct Scout {
         default_value_t = false
     )]
     pub debug: bool,
+
+    #[clap(
+        long,
+        value_name = "path",
+        help = "Directory for build artifacts of the analyzed crate and the detectors. Defaults to `CARGO_TARGET_DIR`."
+    )]
+    pub target_dir: Option<PathBuf>,
+
+    #[clap(
+        long,
+        help = "Don't rebuild the standard library when checking wasm contracts (`-Zbuild-std`)."
+    )]
+    pub no_build_std: bool,
+
+    #[clap(
+        long,
+        help = "Keep the default features when checking wasm contracts, which are otherwise disabled with `--no-default-features`."
+    )]
+    pub keep_default_features: bool,
+
+    #[clap(
+        long,
+        value_name = "path",
+        help = "Save dylint's raw JSON output to the given path, to debug missing findings."
+    )]
+    pub save_raw_output: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "path",
+        help = "Write JSON logs of the run, with the timing of each phase, to the given file."
+    )]
+    pub log_file: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "level",
+        requires = "log_file",
+        help = "Level of the --log-file logs, as in `RUST_LOG` (default: debug)."
+    )]
+    pub log_level: Option<String>,
 }
 
 impl Scout {
-    fn prepare_args(&mut self) {
-        if !self.args.iter().any(|x| x.contains("--target=")) {
-            self.args.extend([
-                "--target=wasm32-unknown-unknown".to_string(),
-                "--no-default-features".to_string(),
-                "-Zbuild-std=std,core,alloc".to_string(),
-            ]);
+    fn prepare_args(&mut self, blockchain: &BlockChain) {
+        if blockchain.requires_wasm_target() && !self.args.iter().any(|x| x.contains("--target=")) {
+            self.args
+                .push("--target=wasm32-unknown-unknown".to_string());
+            if !self.keep_default_features {
+                self.args.push("--no-default-features".to_string());
+            }
+            // A `-Zbuild-std` passed after `--` replaces the default one
+            if !self.no_build_std && !self.args.iter().any(|x| x.starts_with("-Zbuild-std")) {
+                self.args.push("-Zbuild-std=std,core,alloc".to_string());
+            }
         }
         if !self.debug {
             self.args.push("--release".to_string());
@@ -174,16 +693,84 @@ impl Scout {
         if self.filter.is_some() && self.profile.is_some() {
             bail!("The flags `--filter` and `--profile` can't be used together");
         }
+        if self.file.is_some() && self.manifest_path.is_some() {
+            bail!("The flags `--file` and `--manifest-path` can't be used together");
+        }
+        if self.file.is_some() && self.metadata_file.is_some() {
+            bail!("The flags `--file` and `--metadata-file` can't be used together");
+        }
+        if self.git.is_some() && self.file.is_some() {
+            bail!("The flags `--git` and `--file` can't be used together");
+        }
+        if self.git.is_some() && self.metadata_file.is_some() {
+            bail!("The flags `--git` and `--metadata-file` can't be used together");
+        }
+        if let Some(id) = self.deny.iter().find(|id| {
+            self.allow
+                .iter()
+                .any(|allowed| lint_level_id(allowed) == lint_level_id(id))
+        }) {
+            bail!("The detector '{}' can't be both denied and allowed", id);
+        }
+        if self.detectors_ref.is_some() && self.local_detectors.is_some() {
+            bail!("The flags `--detectors-ref` and `--local-detectors` can't be used together");
+        }
         if let Some(path) = &self.output_path {
             if path.is_dir() {
                 bail!("The output path can't be a directory");
             }
         }
+        // Reports are written concurrently, so two of them can't share a file
+        let mut paths = HashMap::<PathBuf, &OutputFormat>::new();
+        for format in self.output_format.iter() {
+            let path = self
+                .output_path
+                .clone()
+                .unwrap_or_else(|| format.default_path());
+            if let Some(other) = paths.insert(path.clone(), format) {
+                if other != format {
+                    bail!(
+                        "The {:?} and {:?} reports would both be written to {}. Generate them in separate runs or with a single format and `--output-path`.",
+                        other,
+                        format,
+                        path.display()
+                    );
+                }
+            }
+        }
         Ok(())
     }
 }
 
-fn get_project_metadata(manifest_path: &Option<PathBuf>) -> Result<Metadata> {
+fn read_metadata_file(path: &Path) -> Result<Metadata> {
+    let content = if path == Path::new("-") {
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut content)
+            .with_context(|| "Failed to read cargo metadata from stdin")?;
+        content
+    } else {
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read metadata file: {}", path.display()))?
+    };
+
+    from_str::<Metadata>(&content).map_err(|e| {
+        anyhow!(
+            "The metadata file is not valid `cargo metadata --format-version 1` output.\n     → Metadata file: {}\n\n     → Caused by: {}",
+            path.display(),
+            e
+        )
+    })
+}
+
+fn get_project_metadata(
+    manifest_path: &Option<PathBuf>,
+    metadata_file: Option<&Path>,
+    lock_flags: LockFlags,
+) -> Result<Metadata> {
+    if let Some(path) = metadata_file {
+        return read_metadata_file(path);
+    }
+
     let mut metadata_command = MetadataCommand::new();
 
     if let Some(manifest_path) = manifest_path {
@@ -203,178 +790,691 @@ fn get_project_metadata(manifest_path: &Option<PathBuf>) -> Result<Metadata> {
     }
 
     metadata_command
+        .other_options(lock_flags.args())
         .exec()
         .map_err(|e| {
             anyhow!("Failed to execute metadata command on this path, ensure this is a valid rust project or workspace directory.\n\n     → Caused by: {}", e.to_string())})
 }
 
-fn temp_file_to_string(mut file: NamedTempFile) -> Result<String> {
-    let mut ret = String::new();
-    std::io::Read::read_to_string(&mut file, &mut ret)?;
-    let _ = file.close();
-    Ok(ret)
+// The dylint output can be huge, so it's parsed a line at a time instead of being read whole.
+fn for_each_output_line(path: &Path, mut f: impl FnMut(Value)) -> Result<()> {
+    let file = fs::File::open(path).with_context(|| "Failed to open dylint output")?;
+    for line in BufReader::new(file).lines() {
+        let line = line.with_context(|| "Failed to read dylint output")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        f(from_str::<Value>(&line).with_context(|| "Failed to parse dylint output")?);
+    }
+    Ok(())
 }
 
-fn output_to_json(output: &str) -> Vec<Value> {
-    output
-        .lines()
-        .map(|line| from_str::<Value>(line).unwrap())
-        .collect::<Vec<Value>>()
+fn output_to_json(path: &Path) -> Result<Vec<Value>> {
+    let mut output = Vec::new();
+    for_each_output_line(path, |value| output.push(value))?;
+    Ok(output)
 }
 
 fn get_crate_from_finding(finding: &Value) -> Option<String> {
     json_to_string_opt(finding.get("target").and_then(|x| x.get("name")))
 }
 
-//In some cases, rustc (or dylint, or clipply, or whoever) has returned the
-//package name where it should be returning the crate name. If you run into
-//problems in the future, try removing the call to this function.
-fn normalize_crate_name(s: &str) -> String {
-    let mut ret = String::new();
-    ret.reserve(s.len());
-    for c in s.chars() {
-        ret.push(if c == '-' { '_' } else { c });
-    }
-    ret
+// The rendered compiler errors, as cargo would have printed them
+fn get_compilation_errors(output: &Path) -> Result<Vec<String>> {
+    let mut ret = Vec::new();
+
+    for_each_output_line(output, |val| {
+        if val.get("reason").and_then(Value::as_str) != Some("compiler-message") {
+            return;
+        }
+        let Some(message) = val.get("message") else {
+            return;
+        };
+        if message.get("level").and_then(Value::as_str) != Some("error") {
+            return;
+        }
+        if let Some(rendered) = json_to_string_opt(message.get("rendered")) {
+            ret.push(rendered);
+        }
+    })?;
+
+    Ok(ret)
 }
 
-fn get_crates_from_output(output: &Vec<Value>) -> HashMap<String, bool> {
+fn get_crates_from_output(output: &Path) -> Result<HashMap<String, bool>> {
     let mut ret = HashMap::<String, bool>::new();
 
-    for val in output {
+    for_each_output_line(output, |val| {
         let reason = val.get("reason");
         let message = val.get("message");
         if reason.is_none() || message.is_none() || reason.unwrap() != "compiler-message" {
-            continue;
+            return;
         }
         let message = message.unwrap();
 
-        let name = get_crate_from_finding(val);
+        let name = get_crate_from_finding(&val);
         if name.is_none() {
-            continue;
+            return;
         }
         let name = normalize_crate_name(&name.unwrap());
         if let Some(previous) = ret.get(&name) {
             if !previous {
-                continue;
+                return;
             }
         }
         let level = message.get("level");
         let ok = level.is_none() || level.unwrap() != "error";
         ret.insert(name, ok);
+    })?;
+
+    Ok(ret)
+}
+
+fn get_crates_from_findings(findings: &Vec<String>) -> HashSet<String> {
+    let mut ret = HashSet::<String>::new();
+
+    for s in findings {
+        let value = from_str::<Value>(s).unwrap();
+        let krate = json_to_string(value.get("crate").unwrap());
+        ret.insert(krate);
+    }
+
+    ret
+}
+
+fn get_crates(
+    output: &Path,
+    findings: &Vec<String>,
+    packages: &[crate::output::report::Package],
+) -> Result<HashMap<String, bool>> {
+    let mut ret = HashMap::<String, bool>::new();
+    for package in packages.iter() {
+        ret.insert(normalize_crate_name(&package.name), true);
+    }
+    for (name, ok) in get_crates_from_output(output)?.iter() {
+        if ret.contains_key(name) {
+            ret.insert(name.clone(), *ok);
+        }
+    }
+    for krate in get_crates_from_findings(findings) {
+        ret.entry(krate).or_insert(true);
+    }
+
+    Ok(ret)
+}
+
+fn split_findings(
+    raw_findings: Vec<String>,
+    crates: &HashMap<String, bool>,
+    packages: &[String],
+) -> (Vec<Value>, Vec<Value>) {
+    let packages: HashSet<String> = packages.iter().map(|p| normalize_crate_name(p)).collect();
+
+    let mut findings = Vec::new();
+
+    for s in raw_findings.iter() {
+        if s.trim().is_empty() {
+            continue;
+        }
+        let value = from_str::<Value>(s).unwrap();
+        findings.push(value);
+    }
+    let mut successful_findings = Vec::<Value>::new();
+    let mut failed_findings = Vec::<Value>::new();
+
+    for finding in findings.iter() {
+        let krate = finding.get("crate");
+        let message = finding.get("message");
+        if krate.is_none() || message.is_none() {
+            continue;
+        }
+        let krate = json_to_string(krate.unwrap());
+        if !packages.is_empty() && !packages.contains(&normalize_crate_name(&krate)) {
+            continue;
+        }
+        let message = message.unwrap();
+        let mut message = message.clone();
+        message["crate"] = Value::String(krate.clone());
+        if *crates.get(&krate).unwrap_or(&true) {
+            &mut successful_findings
+        } else {
+            &mut failed_findings
+        }
+        .push(message);
+    }
+
+    (successful_findings, failed_findings)
+}
+
+// What drops a finding from the report, shared by the reports and the NDJSON stream.
+#[derive(Clone)]
+struct FindingFilters {
+    packages: Vec<String>,
+    ignore_path: Vec<String>,
+    min_confidence: Option<Confidence>,
+    detectors_info: HashMap<String, LintInfo>,
+    dev_only: HashSet<String>,
+    changed: Option<HashSet<PathBuf>>,
+    workspace_root: PathBuf,
+}
+
+impl FindingFilters {
+    fn new(
+        opts: &Scout,
+        metadata: &Metadata,
+        project_info: &ProjectInfo,
+        detectors_info: &HashMap<String, LintInfo>,
+    ) -> Result<Self> {
+        // Checked up front, so the stream can't fail on them
+        for pattern in opts.ignore_path.iter() {
+            glob_regex(pattern)?;
+        }
+        // Test helpers aren't part of what gets audited
+        let dev_only = if opts.include_dev_deps {
+            HashSet::new()
+        } else {
+            dev_only_packages(metadata)
+                .iter()
+                .map(|name| normalize_crate_name(name))
+                .collect()
+        };
+        // The whole crate is still compiled; only the reported findings are narrowed down
+        let changed = if opts.changed_only || opts.since.is_some() {
+            Some(match &opts.changed_files {
+                Some(list) => read_changed_files(list, &project_info.workspace_root)?,
+                None => git_changed_files(
+                    &project_info.workspace_root,
+                    opts.since.as_deref().unwrap_or("HEAD"),
+                )?,
+            })
+        } else {
+            None
+        };
+
+        Ok(FindingFilters {
+            packages: opts.package.clone(),
+            ignore_path: opts.ignore_path.clone(),
+            min_confidence: opts.min_confidence,
+            detectors_info: detectors_info.clone(),
+            dev_only,
+            changed,
+            workspace_root: project_info.workspace_root.clone(),
+        })
+    }
+
+    // Everything but the changed files, which are applied after post-processing.
+    fn apply(&self, findings: Vec<Value>) -> Result<(Vec<Value>, Vec<SuppressedFinding>)> {
+        let packages: HashSet<String> = self
+            .packages
+            .iter()
+            .map(|p| normalize_crate_name(p))
+            .collect();
+        let findings = findings
+            .into_iter()
+            .filter(|finding| {
+                json_to_string_opt(finding.get("crate")).map_or(true, |krate| {
+                    let krate = normalize_crate_name(&krate);
+                    (packages.is_empty() || packages.contains(&krate))
+                        && !self.dev_only.contains(&krate)
+                })
+            })
+            .collect();
+        let findings = filter_ignored_paths(findings, &self.ignore_path, &self.workspace_root)?;
+        let findings = match self.min_confidence {
+            Some(min) => filter_by_confidence(findings, min, &self.detectors_info),
+            None => findings,
+        };
+        Ok(split_suppressed(findings, &self.workspace_root))
+    }
+
+    fn only_changed(&self, findings: Vec<Value>) -> Vec<Value> {
+        match &self.changed {
+            Some(changed) => filter_changed_findings(findings, changed, &self.workspace_root),
+            None => findings,
+        }
+    }
+
+    // Streamed findings are deduplicated as the report does, keeping the first crate.
+    fn into_stream_filter(self) -> impl FnMut(&Value) -> bool + Send + 'static {
+        let mut streamed = HashSet::new();
+        move |finding: &Value| {
+            let kept = self
+                .apply(vec![finding.clone()])
+                .map(|(kept, _)| self.only_changed(kept))
+                .is_ok_and(|kept| !kept.is_empty());
+            kept && streamed.insert(fingerprint(finding))
+        }
+    }
+}
+
+// NDJSON findings are written while the analysis runs; `-` streams them to stdout.
+fn open_findings_stream(opts: &Scout, filters: &FindingFilters) -> Result<Option<FindingsStream>> {
+    if !opts.output_format.contains(&OutputFormat::Ndjson) {
+        return Ok(None);
+    }
+
+    let keep = filters.clone().into_stream_filter();
+    let path = expand_env_vars(&ndjson_output_path(opts.output_path.as_ref()))?;
+    if path == Path::new("-") {
+        return Ok(Some(FindingsStream::new(Box::new(std::io::stdout()), keep)));
+    }
+
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create output directory: {}", parent.display()))?;
+    }
+    let file = fs::File::create(&path)
+        .with_context(|| format!("Failed to create NDJSON output: {}", path.display()))?;
+    Ok(Some(FindingsStream::new(
+        Box::new(std::io::BufWriter::new(file)),
+        keep,
+    )))
+}
+
+// Nightly of the first release that supports each edition
+const EDITION_NIGHTLIES: [(&str, &str); 3] = [
+    ("2018", "2018-09-14"),
+    ("2021", "2021-07-30"),
+    ("2024", "2024-11-29"),
+];
+
+// Packages on an edition newer than what `toolchain` supports, among the selected ones
+fn unsupported_edition_packages<'a>(
+    metadata: &'a Metadata,
+    selected: &[String],
+    toolchain: &str,
+) -> Vec<&'a cargo_metadata::Package> {
+    let Some(date) = toolchain.strip_prefix("nightly-") else {
+        return Vec::new();
+    };
+    let selected: HashSet<String> = selected.iter().map(|p| normalize_crate_name(p)).collect();
+
+    metadata
+        .workspace_packages()
+        .into_iter()
+        .filter(|package| {
+            selected.is_empty() || selected.contains(&normalize_crate_name(&package.name))
+        })
+        .filter(|package| {
+            let edition = package.edition.to_string();
+            EDITION_NIGHTLIES
+                .iter()
+                .any(|(supported, nightly)| edition == *supported && date < *nightly)
+        })
+        .collect()
+}
+
+// Flags take precedence over their environment variables
+fn server_config(opts: &Scout) -> Result<ServerConfig> {
+    let from_env = |var: &str| std::env::var(var).ok().filter(|value| !value.is_empty());
+
+    let mut config = ServerConfig {
+        port_file: opts.server_port_file.clone(),
+        ..ServerConfig::default()
+    };
+    if let Some(address) = opts.server_address {
+        config.address = address;
+    } else if let Some(address) = from_env(env::SCOUT_BIND_ADDRESS) {
+        config.address = address
+            .parse()
+            .with_context(|| format!("Invalid {} address: {}", env::SCOUT_BIND_ADDRESS, address))?;
+    }
+    config.port = match (opts.server_port, from_env(env::SCOUT_SERVER_PORT)) {
+        (Some(port), _) => Some(port),
+        (None, Some(port)) => Some(
+            port.parse()
+                .with_context(|| format!("Invalid {} port: {}", env::SCOUT_SERVER_PORT, port))?,
+        ),
+        (None, None) => None,
+    };
+
+    Ok(config)
+}
+
+// Detectors are named like their crates, while findings carry the lint id
+fn lint_level_id(detector: &str) -> String {
+    detector.trim().replace('-', "_")
+}
+
+pub(crate) fn ndjson_output_path(output_path: Option<&PathBuf>) -> PathBuf {
+    output_path
+        .cloned()
+        .unwrap_or_else(|| OutputFormat::Ndjson.default_path())
+}
+
+// Details about the detectors of a run that are recorded in the report.
+struct DetectorsDetails {
+    commit: Option<String>,
+    skipped: Vec<SkippedDetector>,
+    toolchains: Vec<ReportToolchain>,
+    coverage: DetectorCoverage,
+}
+
+fn load_severity_map(opts: &Scout) -> Result<SeverityMap> {
+    match &opts.severity_map {
+        Some(path) => SeverityMap::from_file(path),
+        None => Ok(SeverityMap::default()),
+    }
+}
+
+fn report_date(opts: &Scout) -> Result<String> {
+    ProjectInfo::format_date(
+        opts.date_format.as_deref().unwrap_or("%Y-%m-%d"),
+        opts.date_epoch,
+    )
+}
+
+// Analyzes every blockchain of the workspace, so their findings are reported together
+fn scout_all_blockchains(opts: Scout, metadata: &Metadata) -> Result<FindingsBundle> {
+    let mut bundle = analyze_all_blockchains(&opts, metadata, scout)?;
+    let project_info = ProjectInfo::get_project_info(metadata, report_date(&opts)?)
+        .map_err(|err| anyhow!("Failed to get project info.\n\n     → Caused by: {}", err))?;
+    bundle.project_info = Some(project_info);
+    Ok(bundle)
+}
+
+// Runs the analysis in a child process on `toolchain`, which hands it back through a findings
+// bundle, or returns `None` if scout already runs on it
+fn scout_in_nightly(toolchain: &str, opts: &Scout) -> Result<Option<FindingsBundle>> {
+    let bundle_file = NamedTempFile::new().with_context(|| "Failed to create findings bundle")?;
+    let mut child_opts = opts.clone();
+    child_opts.findings_bundle = Some(bundle_file.path().to_path_buf());
+
+    let Some(mut child) = run_scout_in_nightly(toolchain, &child_opts)? else {
+        return Ok(None);
+    };
+    let exit_status = child
+        .wait()
+        .with_context(|| "Failed to wait for nightly child process")?;
+    if !exit_status.success() {
+        bail!(
+            "Scout failed when running with the {} toolchain ({})",
+            toolchain,
+            exit_status
+        );
     }
 
-    ret
+    FindingsBundle::read(bundle_file.path())?
+        .with_context(|| format!("Scout wrote no findings with the {} toolchain", toolchain))
+        .map(Some)
 }
 
-fn get_crates_from_findings(findings: &Vec<String>) -> HashSet<String> {
-    let mut ret = HashSet::<String>::new();
+fn capture_noop<T, E, F: FnOnce() -> Result<T, E>>(
+    cb: F,
+    _stream: Option<FindingsStream>,
+    _config: &ServerConfig,
+) -> Result<(Vec<String>, T), E> {
+    use std::result::Result::Ok;
+    match cb() {
+        Ok(r) => Ok((Vec::<String>::new(), r)),
+        Err(e) => Err(e),
+    }
+}
 
-    for s in findings {
-        let value = from_str::<Value>(s).unwrap();
-        let krate = json_to_string(value.get("crate").unwrap());
-        ret.insert(krate);
+/// Runs scout from the command line: analyzes the project with `scout`, as `analyze` does, and
+/// reports the findings in the requested formats.
+pub fn run_scout(opts: Scout) -> Result<Vec<Value>> {
+    let mut opts = forwarded_options()?.unwrap_or(opts);
+    // Anything else printed to stdout would corrupt the streamed findings
+    if opts.output_format.contains(&OutputFormat::Ndjson)
+        && opts.output_path.as_deref() == Some(Path::new("-"))
+    {
+        opts.quiet = true;
+    }
+    let _print_settings = PrintSettings::apply(
+        opts.quiet,
+        match opts.color {
+            ColorChoice::Auto => std::io::stdout().is_terminal(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        },
+    );
+
+    // A nightly child hands the analysis back to its parent, which reports it
+    if let Some(path) = opts.findings_bundle.clone() {
+        let bundle = scout(opts)?;
+        bundle.write(&path)?;
+        return Ok(bundle.findings);
     }
 
-    ret
-}
+    let started = Instant::now();
+    let status_file = opts.status_file.clone();
+    let stats = opts.stats || (opts.verbose && !opts.quiet);
 
-fn get_crates(
-    output: &Vec<Value>,
-    findings: &Vec<String>,
-    packages: &[crate::output::report::Package],
-) -> HashMap<String, bool> {
-    let mut ret = HashMap::<String, bool>::new();
-    for package in packages.iter() {
-        ret.insert(normalize_crate_name(&package.name), true);
+    let mut status = RunStatus::default();
+    let result = scout(opts.clone()).and_then(|bundle| report_analysis(bundle, opts, &mut status));
+    if let Err(err) = &result {
+        status.exit_status = 1;
+        status.error = Some(err.to_string());
     }
-    for (name, ok) in get_crates_from_output(output).iter() {
-        if ret.contains_key(name) {
-            ret.insert(name.clone(), *ok);
-        }
+    if let Some(status_file) = status_file {
+        status.write(&status_file)?;
     }
-    for krate in get_crates_from_findings(findings) {
-        ret.entry(krate).or_insert(true);
+    if stats {
+        status.print_stats(started.elapsed());
     }
 
-    ret
+    result
 }
 
-fn split_findings(
-    raw_findings: Vec<String>,
-    crates: &HashMap<String, bool>,
-) -> (Vec<Value>, Vec<Value>) {
-    let mut findings = Vec::new();
+/// Analyzes the project as `run_scout` does, but returns the findings instead of printing
+/// or writing any report. Options such as `--toolchain` or `--list` that answer without
+/// analyzing return their output instead.
+///
+/// The environment variables a run needs, such as `CARGO_TARGET_DIR` for `target_dir`, are
+/// set while it lasts and restored afterwards. If the project needs a toolchain other than the
+/// one scout runs on, the analysis runs in a child process: the `cargo-scout-audit` found in
+/// `PATH`, which has to be installed for that.
+pub fn analyze(mut opts: Scout) -> Result<Analysis> {
+    opts.quiet = true;
+    opts.output_format = Vec::new();
+    opts.status_file = None;
+    opts.findings_bundle = None;
+    let _print_settings = PrintSettings::apply(true, false);
+
+    let bundle = scout(opts)?;
+    if bundle.output.is_none() && bundle.crates.is_empty() {
+        bail!("Nothing was analyzed. Check your build system for errors.");
+    }
+    bundle.into_analysis()
+}
 
-    for s in raw_findings.iter() {
-        if s.trim().is_empty() {
-            continue;
-        }
-        let value = from_str::<Value>(s).unwrap();
-        findings.push(value);
+// Reports an analysis in the requested formats, or prints what an option that answers without
+// analyzing produced
+fn report_analysis(
+    bundle: FindingsBundle,
+    opts: Scout,
+    status: &mut RunStatus,
+) -> Result<Vec<Value>> {
+    if let Some(output) = &bundle.output {
+        println!("{}", output);
+        return Ok(vec![]);
     }
-    let mut successful_findings = Vec::<Value>::new();
-    let mut failed_findings = Vec::<Value>::new();
+    let project_info = bundle
+        .project_info
+        .with_context(|| "The findings bundle has no project info")?;
+
+    status.record_findings(
+        &dedup_findings(&bundle.findings),
+        &bundle.crates,
+        &bundle.detectors,
+    );
 
-    for finding in findings.iter() {
-        let krate = finding.get("crate");
-        let message = finding.get("message");
-        if krate.is_none() || message.is_none() {
-            continue;
+    // Each blockchain's run already parsed its own output
+    let inside_vscode =
+        !opts.all_blockchains && opts.args.contains(&"--message-format=json".to_string());
+    let coverage = DetectorCoverage {
+        used: bundle.detectors.len(),
+        available: bundle.available_detectors,
+    };
+    do_report(
+        &bundle.findings,
+        bundle.crates,
+        project_info,
+        bundle.detectors,
+        bundle.raw_output,
+        bundle.suppressed,
+        opts,
+        inside_vscode,
+        DetectorsDetails {
+            commit: bundle.detectors_commit,
+            skipped: bundle.skipped,
+            toolchains: bundle.toolchains,
+            coverage,
+        },
+    )?;
+
+    Ok(bundle.findings)
+}
+
+// Analyzes the project without reporting, which is left to the caller
+#[tracing::instrument(name = "RUN SCOUT", skip_all)]
+fn scout(mut opts: Scout) -> Result<FindingsBundle> {
+    opts.validate()?;
+
+    if let Some(format) = &opts.print_schema {
+        return Ok(FindingsBundle::with_output(get_schema(format)?));
+    }
+
+    let forced_blockchain = opts
+        .blockchain
+        .as_deref()
+        .map(|name| {
+            BlockChain::from_str(name).map_err(|_| {
+                anyhow!(
+                    "Unknown blockchain '{}'. Supported blockchains: {}",
+                    name,
+                    BlockChain::variants().join(", ")
+                )
+            })
+        })
+        .transpose()?;
+
+    // Kept with the findings, as reports read code snippets from them
+    let mut workdirs = Vec::new();
+    if let Some(file) = opts.file.take() {
+        let dir = synthesize_crate(&file, forced_blockchain)?;
+        opts.manifest_path = Some(dir.path().join("Cargo.toml"));
+        workdirs.push(dir);
+    }
+
+    // Findings are relative to the workspace root, so the report doesn't carry the clone path
+    if let Some(url) = opts.git.take() {
+        if !opts.quiet {
+            println!("Cloning {}...", url);
         }
-        let krate = json_to_string(krate.unwrap());
-        let message = message.unwrap();
-        let mut message = message.clone();
-        message["crate"] = Value::String(krate.clone());
-        if *crates.get(&krate).unwrap_or(&true) {
-            &mut successful_findings
-        } else {
-            &mut failed_findings
+        let (dir, root) = clone_repository(&url, opts.rev.take().as_deref())?;
+        let manifest_path = root.join(
+            opts.manifest_path
+                .take()
+                .unwrap_or_else(|| PathBuf::from("Cargo.toml")),
+        );
+        if !manifest_path.is_file() {
+            bail!(
+                "Could not find {} in {}. Use `--manifest-path` to point at it within the repository.",
+                manifest_path
+                    .strip_prefix(&root)
+                    .unwrap_or(&manifest_path)
+                    .display(),
+                url
+            );
         }
-        .push(message);
+        opts.manifest_path = Some(manifest_path);
+        workdirs.push(dir);
     }
 
-    (successful_findings, failed_findings)
-}
+    // Cargo, dylint, the detectors build and the nightly child all read it from the environment,
+    // which is restored once the analysis is done
+    let mut scoped_env = ScopedEnv::default();
+    if let Some(target_dir) = &opts.target_dir {
+        let target_dir = std::env::current_dir()
+            .with_context(|| "Failed to get current directory")?
+            .join(target_dir);
+        scoped_env.set(env::CARGO_TARGET_DIR, target_dir);
+    }
 
-fn capture_noop<T, E, F: FnOnce() -> Result<T, E>>(cb: F) -> Result<(Vec<String>, T), E> {
-    use std::result::Result::Ok;
-    match cb() {
-        Ok(r) => Ok((Vec::<String>::new(), r)),
-        Err(e) => Err(e),
+    // Keeps the cargo invocations made by dylint and the detectors build off the network too
+    let lock_flags = LockFlags::from_args(&opts.args);
+    if lock_flags.offline() {
+        scoped_env.set(env::CARGO_NET_OFFLINE, "true");
     }
-}
 
-#[tracing::instrument(name = "RUN SCOUT", skip_all)]
-pub fn run_scout(mut opts: Scout) -> Result<Vec<Value>> {
-    opts.validate()?;
-    opts.prepare_args();
+    let metadata = get_project_metadata(
+        &opts.manifest_path,
+        opts.metadata_file.as_deref(),
+        lock_flags,
+    )?;
+    // A nightly child can't read stdin again, so it is handed a copy
+    let _stdin_metadata = if opts.metadata_file.as_deref() == Some(Path::new("-")) {
+        let file = NamedTempFile::new().with_context(|| "Failed to create metadata file")?;
+        serde_json::to_writer(&file, &metadata).with_context(|| "Failed to write metadata file")?;
+        opts.metadata_file = Some(file.path().to_path_buf());
+        Some(file)
+    } else {
+        None
+    };
 
-    let metadata = get_project_metadata(&opts.manifest_path)?;
-    let blockchain = BlockChain::get_blockchain_dependency(&metadata)?;
+    if opts.all_blockchains {
+        let mut bundle = scout_all_blockchains(opts, &metadata)?;
+        bundle.workdirs = workdirs;
+        return Ok(bundle);
+    }
+
+    let blockchain = match forced_blockchain {
+        Some(blockchain) => blockchain,
+        None => BlockChain::get_blockchain_dependency(&metadata)?,
+    };
     let toolchain = blockchain.get_toolchain();
 
     if opts.toolchain {
-        println!("{}", toolchain);
-        return Ok(vec![]);
+        return Ok(FindingsBundle::with_output(toolchain.to_string()));
     }
 
-    if let Some(mut child) = run_scout_in_nightly(toolchain)? {
-        child
-            .wait()
-            .with_context(|| "Failed to wait for nightly child process")?;
-        return Ok(vec![]);
+    // The child prepares its own cargo arguments
+    if let Some(mut bundle) = scout_in_nightly(toolchain, &opts)? {
+        bundle.workdirs = workdirs;
+        return Ok(bundle);
     }
+    opts.prepare_args(&blockchain);
 
-    if let Err(e) = VersionChecker::new().check_for_updates() {
-        print_error(&format!(
-            "Failed to check for updates.\n\n     → Caused by: {}",
-            e
+    for package in unsupported_edition_packages(&metadata, &opts.package, toolchain) {
+        print_warning(&format!(
+            "The package '{}' uses the {} edition, which the {} toolchain scout analyzes {} projects with doesn't support. Its build will likely fail.",
+            package.name, package.edition, toolchain, blockchain
         ));
     }
 
-    let cargo_config =
+    if !opts.quiet {
+        if let Err(e) = VersionChecker::new()
+            .with_interval(Duration::from_secs(opts.update_check_interval * 60 * 60))
+            .check_for_updates(opts.check_updates)
+        {
+            print_error(&format!(
+                "Failed to check for updates.\n\n     → Caused by: {}",
+                e
+            ));
+        }
+    }
+
+    let mut cargo_config =
         GlobalContext::default().with_context(|| "Failed to create default cargo configuration")?;
+    // Fetching git detectors goes through cargo's own sources
+    cargo_config
+        .configure(
+            0,
+            false,
+            None,
+            lock_flags.frozen,
+            lock_flags.locked(),
+            lock_flags.offline(),
+            &None,
+            &[],
+            &[],
+        )
+        .with_context(|| "Failed to configure cargo")?;
     cargo_config.shell().set_verbosity(if opts.verbose {
         Verbosity::Verbose
     } else {
@@ -388,30 +1488,77 @@ pub fn run_scout(mut opts: Scout) -> Result<Vec<Value>> {
                 e
             )
         })?,
-        None => {
-            get_remote_detectors_configuration(blockchain, opts.force_fallback).map_err(|e| {
-                anyhow!(
-                    "Failed to get remote detectors configuration.\n\n     → Caused by: {}",
-                    e
-                )
-            })?
-        }
+        None => get_remote_detectors_configuration(
+            blockchain,
+            opts.force_fallback,
+            opts.detectors_ref.as_deref(),
+        )
+        .map_err(|e| {
+            anyhow!(
+                "Failed to get remote detectors configuration.\n\n     → Caused by: {}",
+                e
+            )
+        })?,
     };
 
     // Instantiate detectors
+    let inside_vscode = opts.args.contains(&"--message-format=json".to_string());
+    let show_progress = !opts.quiet && progress::is_enabled(inside_vscode);
+
     let detector_builder = DetectorBuilder::new(
         &cargo_config,
         &detectors_config,
-        &metadata,
         opts.verbose,
         toolchain,
+        opts.jobs,
+        lock_flags,
+        show_progress,
     );
 
     let detectors_names = detector_builder
         .get_detector_names()
         .map_err(|e| anyhow!("Failed to get detector names.\n\n     → Caused by: {}", e))?;
 
-    let profile_detectors = match &opts.profile {
+    if opts.list_profiles {
+        let profiles = get_profiles(blockchain, &detectors_names)
+            .map_err(|err| anyhow!("Failed to read profiles.\n\n     → Caused by: {}", err))?;
+        return Ok(FindingsBundle::with_output(list_profiles(&profiles)));
+    }
+
+    if let Some(profile) = &opts.config_profile_generate {
+        let config_path =
+            generate_profile(blockchain, profile, &detectors_names).map_err(|err| {
+                anyhow!(
+                    "Failed to generate profile.
+
+     → Caused by: {}",
+                    err
+                )
+            })?;
+        return Ok(FindingsBundle::with_output(format!(
+            "Profile '{}' written to {}. Set `enabled` to false for the detectors to skip, then run with `--profile {}`.",
+            profile,
+            config_path.display(),
+            profile
+        )));
+    }
+
+    let severity_map = load_severity_map(&opts)?;
+
+    // Every detector is checked, whatever the profile or filters
+    if opts.self_test {
+        let detectors_paths = detector_builder
+            .build(&blockchain, &detectors_names)
+            .map_err(|e| anyhow!("Failed to build detectors.\n\n     → Caused by: {}", e))?;
+        let (detectors_info, _, skipped) = get_detectors_info(&detectors_paths, &blockchain)?;
+        return Ok(FindingsBundle::with_output(self_test_detectors(
+            &detectors_info,
+            &skipped,
+            &severity_map,
+        )?));
+    }
+
+    let (profile_detectors, severity_overrides) = match &opts.profile {
         Some(profile) => {
             let (config, config_path) =
                 open_config_and_sync_detectors(blockchain, &detectors_names).map_err(|err| {
@@ -427,24 +1574,51 @@ pub fn run_scout(mut opts: Scout) -> Result<Vec<Value>> {
                 config_path.display()
             ));
 
-            profile_enabled_detectors(&config, profile, &config_path, &detectors_names)?
+            (
+                profile_enabled_detectors(&config, profile, &config_path, &detectors_names)?,
+                profile_severity_overrides(&config, profile, &severity_map)?,
+            )
         }
-        None => detectors_names.clone(),
+        None => (detectors_names.clone(), HashMap::new()),
     };
 
     if opts.list_detectors {
-        list_detectors(&profile_detectors);
-        return Ok(vec![]);
+        let enabled = opts.profile.as_ref().map(|_| profile_detectors.as_slice());
+        return Ok(FindingsBundle::with_output(list_detectors(
+            &detectors_names,
+            enabled,
+        )));
     }
 
     let filtered_detectors = if let Some(filter) = &opts.filter {
-        get_filtered_detectors(filter, &profile_detectors)?
+        get_filtered_detectors(filter, &profile_detectors, opts.strict)?
     } else if let Some(excluded) = &opts.exclude {
-        get_excluded_detectors(excluded, &profile_detectors)
+        get_excluded_detectors(excluded, &profile_detectors, opts.strict)?
     } else {
         profile_detectors
     };
 
+    if filtered_detectors.is_empty() {
+        bail!(
+            "The combination of --filter, --exclude and --profile selected no detectors.\n\n     Available detectors: {}",
+            detectors_names.join(", ")
+        );
+    }
+
+    if opts.verbose && !opts.quiet {
+        let unused: Vec<&str> = detectors_names
+            .iter()
+            .filter(|name| !filtered_detectors.contains(name))
+            .map(String::as_str)
+            .collect();
+        if !unused.is_empty() {
+            println!(
+                "Detectors left out by --filter, --exclude or --profile: {}",
+                unused.join(", ")
+            );
+        }
+    }
+
     let detectors_paths = detector_builder
         .build(&blockchain, &filtered_detectors)
         .map_err(|e| {
@@ -454,18 +1628,119 @@ pub fn run_scout(mut opts: Scout) -> Result<Vec<Value>> {
             )
         })?;
 
-    let (detectors_info, custom_detectors) = get_detectors_info(&detectors_paths)?;
+    // Listing the detectors' metadata doesn't need their libraries loaded
+    let metadata_only = opts.explain.is_some() || opts.list_categories || opts.detectors_metadata;
+    let (mut detectors_info, custom_detectors, skipped_detectors) = if metadata_only {
+        let (detectors_info, skipped) = get_cached_detectors_info(&detectors_paths, &blockchain)?;
+        (detectors_info, HashMap::new(), skipped)
+    } else {
+        get_detectors_info(&detectors_paths, &blockchain)?
+    };
+    // Custom labels are resolved once, so the rest of the run only sees built-in severities
+    for (id, info) in detectors_info.iter_mut() {
+        let severity = severity_overrides
+            .get(id)
+            .copied()
+            .or_else(|| severity_map.severity(&info.severity));
+        match severity {
+            Some(severity) => info.severity = severity.label().to_string(),
+            None => print_warning(&format!(
+                "The detector '{}' has an unknown severity '{}'.",
+                info.id, info.severity
+            )),
+        }
+    }
+    for skipped in skipped_detectors.iter() {
+        print_warning(&format!("Skipping detector: {}", skipped.error));
+    }
+
+    // Broken detectors are left out of the analysis
+    let loaded_paths: Vec<PathBuf> = detectors_paths
+        .iter()
+        .filter(|path| {
+            !skipped_detectors
+                .iter()
+                .any(|skipped| &skipped.path == *path)
+        })
+        .cloned()
+        .collect();
+
+    let detectors = DetectorsDetails {
+        commit: detector_builder.get_detectors_commit(),
+        skipped: skipped_detectors,
+        toolchains: vec![ReportToolchain {
+            blockchain: blockchain.to_string(),
+            toolchain: toolchain.to_string(),
+        }],
+        coverage: DetectorCoverage {
+            used: detectors_info.len(),
+            available: detectors_names.len(),
+        },
+    };
+
+    if let Some(id) = &opts.explain {
+        return Ok(FindingsBundle::with_output(explain_detector(
+            id,
+            &detectors_info,
+        )?));
+    }
+
+    if opts.list_categories {
+        return Ok(FindingsBundle::with_output(list_categories(
+            &detectors_info,
+        )));
+    }
 
     if opts.detectors_metadata {
-        let json = to_string_pretty(&detectors_info);
-        println!("{}", json.unwrap());
-        return Ok(vec![]);
+        let metadata = serde_json::json!({
+            "scout_version": env!("CARGO_PKG_VERSION"),
+            "blockchain": blockchain.to_string(),
+            "toolchain": toolchain,
+            "detectors_commit": detectors.commit,
+            "skipped_detectors": detectors.skipped,
+            "detectors": detectors_info,
+        });
+        return Ok(FindingsBundle::with_output(to_string_pretty(&metadata)?));
     }
 
-    let project_info = ProjectInfo::get_project_info(&metadata)
+    let project_info = ProjectInfo::get_project_info(&metadata, report_date(&opts)?)
         .map_err(|err| anyhow!("Failed to get project info.\n\n     → Caused by: {}", err))?;
 
-    let inside_vscode = opts.args.contains(&"--message-format=json".to_string());
+    for package in opts.package.iter() {
+        if !project_info
+            .packages
+            .iter()
+            .any(|p| normalize_crate_name(&p.name) == normalize_crate_name(package))
+        {
+            bail!("Package `{}` is not a member of this workspace", package);
+        }
+    }
+
+    // Resolved to packages, so cargo only checks the matching crates
+    if !opts.only_crates.is_empty() {
+        let only_crates = crate_matcher(&opts.only_crates)?;
+        let selected: HashSet<String> = opts
+            .package
+            .iter()
+            .map(|p| normalize_crate_name(p))
+            .collect();
+        let packages: Vec<String> = project_info
+            .packages
+            .iter()
+            .map(|p| p.name.clone())
+            .filter(|name| selected.is_empty() || selected.contains(&normalize_crate_name(name)))
+            .filter(|name| only_crates(name.as_str()))
+            .collect();
+        if packages.is_empty() {
+            bail!(
+                "No workspace package matches --only-crates {}",
+                opts.only_crates.join(", ")
+            );
+        }
+        opts.package = packages;
+    }
+
+    let filters = FindingFilters::new(&opts, &metadata, &project_info, &detectors_info)?;
 
     let wrapper_function = if inside_vscode {
         capture_noop
@@ -473,51 +1748,116 @@ pub fn run_scout(mut opts: Scout) -> Result<Vec<Value>> {
         capture_output
     };
 
-    let (findings, (_failed_build, stdout)) = wrapper_function(|| {
-        // Run dylint
-        run_dylint(
-            detectors_paths.clone(),
-            &opts,
-            &metadata,
-            inside_vscode,
-            &custom_detectors,
-        )
-        .map_err(|err| anyhow!("Failed to run dylint.\n\n     → Caused by: {}", err))
-    })?;
+    let (findings, stdout) = if opts.merge.is_empty() {
+        let analysis_progress = progress::spinner(
+            show_progress && !opts.verbose,
+            &format!("Analyzing project with {} detectors", loaded_paths.len()),
+        );
+        let stream = open_findings_stream(&opts, &filters)?;
+        let server_config = server_config(&opts)?;
+        let analysis = wrapper_function(
+            || {
+                // Run dylint
+                run_dylint(
+                    loaded_paths.clone(),
+                    &opts,
+                    &metadata,
+                    inside_vscode,
+                    &custom_detectors,
+                )
+                .map_err(|err| anyhow!("Failed to run dylint.\n\n     → Caused by: {}", err))
+            },
+            stream,
+            &server_config,
+        );
+        analysis_progress.finish_and_clear();
+        let (findings, (_failed_build, stdout)) = analysis?;
+        (findings, stdout)
+    } else {
+        let findings = merge_raw_reports(&opts.merge, open_findings_stream(&opts, &filters)?)?;
+        // The merged reports were analyzed elsewhere, so there's no dylint output
+        let stdout = NamedTempFile::new().with_context(|| "Failed to create dylint output")?;
+        (findings, stdout)
+    };
 
-    let output_string = temp_file_to_string(stdout)?;
-    //println!("{}", output_string);
-    let output = output_to_json(&output_string);
-    let crates = get_crates(&output, &findings, &project_info.packages);
+    // Copied before parsing, so the output is kept even if it can't be parsed
+    if let Some(path) = &opts.save_raw_output {
+        fs::copy(stdout.path(), path)
+            .with_context(|| format!("Failed to save raw output to {}", path.display()))?;
+    }
+
+    // Only VS Code gets the output as is
+    let output_string = if inside_vscode {
+        fs::read_to_string(stdout.path()).with_context(|| "Failed to read dylint output")?
+    } else {
+        String::new()
+    };
+    let mut crates = get_crates(stdout.path(), &findings, &project_info.packages)?;
+    if !opts.package.is_empty() {
+        let selected: HashSet<String> = opts
+            .package
+            .iter()
+            .map(|p| normalize_crate_name(p))
+            .collect();
+        crates.retain(|name, _| selected.contains(name));
+    }
+
+    if opts.fail_fast {
+        let mut failed: Vec<&String> = crates
+            .iter()
+            .filter(|(_, ok)| !**ok)
+            .map(|(name, _)| name)
+            .collect();
+        if !failed.is_empty() {
+            failed.sort();
+            for error in get_compilation_errors(stdout.path())? {
+                eprint!("{}", error);
+            }
+            bail!(
+                "{} {} failed to compile: {}",
+                failed.len(),
+                if failed.len() == 1 { "crate" } else { "crates" },
+                failed
+                    .iter()
+                    .map(|name| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
 
-    if crates.is_empty() && !inside_vscode {
+    // The reports are still written, with an `incomplete` status
+    if crates.is_empty() && !inside_vscode && !opts.quiet {
         let string = OutputFormatter::new()
             .fg()
             .red()
             .text_str("Nothing was analyzed. Check your build system for errors.")
             .print();
         println!("{}", string);
-        return Ok(vec![]);
     }
 
-    let (successful_findings, _failed_findings) = split_findings(findings, &crates);
+    let (successful_findings, _failed_findings) = split_findings(findings, &crates, &opts.package);
+    let (successful_findings, suppressed) = filters.apply(successful_findings)?;
 
     // Get the path of the 'unnecessary_lint_allow' detector
-    let unnecessary_lint_allow_path = detectors_paths.iter().find_map(|path| {
-        path.to_str()
-            .filter(|s| s.contains("unnecessary_lint_allow"))
-            .map(|_| path)
-    });
+    // Merged findings come without the output it works on
+    let unnecessary_lint_allow_path = loaded_paths
+        .iter()
+        .filter(|_| opts.merge.is_empty())
+        .find_map(|path| {
+            path.to_str()
+                .filter(|s| s.contains("unnecessary_lint_allow"))
+                .map(|_| path)
+        });
 
     // Create and run post processor if the path is found, otherwise use default values
     let (console_findings, output_string_vscode) = if let Some(path) = unnecessary_lint_allow_path {
+        // The post processor needs the whole output
         match PostProcessing::new(path) {
             std::result::Result::Ok(post_processor) => {
-                match post_processor.process(
-                    successful_findings.clone(),
-                    output.clone(),
-                    inside_vscode,
-                ) {
+                match output_to_json(stdout.path()).and_then(|output| {
+                    post_processor.process(successful_findings.clone(), output, inside_vscode)
+                }) {
                     std::result::Result::Ok(result) => result,
                     Err(e) => {
                         print_error(&format!("Error running post process: {}", e));
@@ -533,28 +1873,36 @@ pub fn run_scout(mut opts: Scout) -> Result<Vec<Value>> {
     } else {
         (successful_findings, output_string)
     };
-    // Generate report
-    do_report(
-        &console_findings,
-        crates,
-        project_info,
-        detectors_info,
-        output_string_vscode,
-        opts,
-        inside_vscode,
-    )?;
 
-    Ok(console_findings)
+    let console_findings = filters.only_changed(console_findings);
+
+    Ok(FindingsBundle {
+        findings: console_findings,
+        detectors: detectors_info,
+        crates,
+        skipped: detectors.skipped,
+        suppressed,
+        project_info: Some(project_info),
+        available_detectors: detectors.coverage.available,
+        detectors_commit: detectors.commit,
+        toolchains: detectors.toolchains,
+        raw_output: output_string_vscode,
+        output: None,
+        workdirs,
+    })
 }
 
+#[allow(clippy::too_many_arguments)]
 fn do_report(
     findings: &Vec<Value>,
     crates: HashMap<String, bool>,
     project_info: ProjectInfo,
     detectors_info: HashMap<String, LintInfo>,
     output_string: String,
+    suppressed: Vec<SuppressedFinding>,
     opts: Scout,
     inside_vscode: bool,
+    detectors: DetectorsDetails,
 ) -> Result<()> {
     if inside_vscode {
         std::io::stdout()
@@ -562,15 +1910,242 @@ fn do_report(
             .write_all(output_string.as_bytes())
             .with_context(|| ("Failed to write stdout content"))?;
     } else {
-        crate::output::console::render_report(findings, &crates, &detectors_info)?;
-        generate_report(
-            findings,
-            &crates,
-            project_info,
-            &detectors_info,
+        // The same finding can be reported once per workspace crate that compiles the file
+        let mut findings = dedup_findings(findings);
+        sort_findings(&mut findings);
+        let findings = &findings;
+
+        let diff = match &opts.compare_to {
+            Some(path) => {
+                let previous = read_raw_report(path)?;
+                let diff = diff_findings(&previous, findings, path);
+                if opts.baseline_update_on_fix && !diff.fixed.is_empty() {
+                    prune_fixed_findings(path, &previous, &diff)?;
+                    if !opts.quiet {
+                        println!(
+                            "Removed {} fixed findings from {}:",
+                            diff.fixed.len(),
+                            path.display()
+                        );
+                        for entry in diff.fixed.iter() {
+                            println!("  - {} at {}", entry.vulnerability_id, entry.location);
+                        }
+                    }
+                }
+                Some(diff)
+            }
+            None => None,
+        };
+
+        let severity_map = load_severity_map(&opts)?;
+        let (shown_findings, omitted) = match opts.max_findings {
+            Some(max) => truncate_findings(findings, max, &detectors_info, &severity_map),
+            None => (findings.clone(), 0),
+        };
+
+        if !opts.quiet {
+            crate::output::console::render_report(
+                &shown_findings,
+                &crates,
+                &detectors_info,
+                opts.template_dir.as_deref(),
+                opts.show_detector_id,
+                opts.summary_only,
+                opts.format,
+            )?;
+            println!(
+                "Coverage: {}/{} detectors.",
+                detectors.coverage.used, detectors.coverage.available
+            );
+
+            if omitted > 0 {
+                print_warning(&format!(
+                    "{} findings omitted. Only the {} most severe findings are shown.",
+                    omitted,
+                    shown_findings.len()
+                ));
+            }
+
+            if !suppressed.is_empty() {
+                println!(
+                    "{} findings suppressed by `scout-audit:ignore` comments.",
+                    suppressed.len()
+                );
+            }
+
+            if opts.verbose {
+                let clean = clean_detectors(detectors_info.keys(), findings);
+                if !clean.is_empty() {
+                    println!("Detectors with no findings: {}", clean.join(", "));
+                }
+            }
+
+            if let Some(diff) = &diff {
+                println!(
+                    "Compared to {}: {} new, {} fixed, {} unchanged findings.",
+                    diff.compared_to,
+                    diff.new.len(),
+                    diff.fixed.len(),
+                    diff.unchanged.len()
+                );
+            }
+        }
+
+        let allowed: HashSet<String> = opts.allow.iter().map(|id| lint_level_id(id)).collect();
+        let denied: HashSet<String> = opts.deny.iter().map(|id| lint_level_id(id)).collect();
+        for id in allowed.union(&denied) {
+            if !detectors_info.contains_key(id) {
+                print_warning(&format!(
+                    "The detector '{}' given to --deny or --allow is not loaded.",
+                    id
+                ));
+            }
+        }
+
+        // Allowed detectors are still reported, they just don't fail the run
+        let new_findings = diff.as_ref().map_or(0, |diff| {
+            diff.new
+                .iter()
+                .filter(|entry| !allowed.contains(&entry.vulnerability_id))
+                .count()
+        });
+        let denied_findings: Vec<String> = findings
+            .iter()
+            .filter_map(lint_id)
+            .filter(|id| denied.contains(id))
+            .collect();
+        // Findings were already narrowed down to the changed files
+        let changed_findings = opts.since.clone().map(|since| {
+            let count = dedup_findings(findings)
+                .iter()
+                .filter(|finding| lint_id(finding).map_or(true, |id| !allowed.contains(&id)))
+                .count();
+            (since, count)
+        });
+
+        // Only the console output above uses the rendered diagnostics
+        let (findings, shown_findings) = if opts.strip_rendered {
+            (strip_rendered(findings), strip_rendered(&shown_findings))
+        } else {
+            (findings.clone(), shown_findings)
+        };
+        let findings = &findings;
+
+        let build_report = |reported: &[Value], omitted_findings: usize| -> Result<Report> {
+            let mut report =
+                RawReport::generate_report(reported, &crates, &project_info, &detectors_info)?;
+            // Omitted findings still count, or their detectors would be reported as clean
+            report.clean_detectors = clean_detectors(detectors_info.keys(), findings);
+            report.status = ReportStatus::from_findings(findings, &crates);
+            report.diff = diff.clone();
+            report.detectors_ref = detectors.commit.clone();
+            report.skipped_detectors = detectors.skipped.clone();
+            report.detector_coverage = Some(detectors.coverage);
+            report.suppressed_findings = suppressed.clone();
+            report.omitted_findings = omitted_findings;
+            report.severity_map = severity_map.clone();
+            report.summary_only = opts.summary_only;
+            if let Some(title) = &opts.report_title {
+                report.title = title.clone();
+            }
+            report.author = opts.report_author.clone();
+            if opts.embed_metadata {
+                report.metadata = Some(ReportMetadata {
+                    scout_version: env!("CARGO_PKG_VERSION").to_string(),
+                    toolchains: detectors.toolchains.clone(),
+                    workspace_root: project_info.workspace_root.clone(),
+                    detectors: detectors_info
+                        .iter()
+                        .map(|(id, info)| (id.clone(), info.clone()))
+                        .collect(),
+                });
+            }
+            Ok(report)
+        };
+        // Findings over the limit are left out, unless the JSON reports are asked to keep them.
+        // Formats with no room to note the omitted findings keep them all.
+        let (full_formats, truncated_formats): (Vec<OutputFormat>, Vec<OutputFormat>) =
+            opts.output_format.iter().cloned().partition(|format| {
+                omitted > 0
+                    && (matches!(
+                        format,
+                        OutputFormat::RawJson
+                            | OutputFormat::CodeClimate
+                            | OutputFormat::JsonByFile
+                    ) || (opts.no_truncate_json
+                        && matches!(format, OutputFormat::Json | OutputFormat::Toml)))
+            });
+
+        let mut report_paths = Vec::new();
+        if !full_formats.is_empty() {
+            report_paths = generate_report(
+                &build_report(findings, 0)?,
+                findings,
+                opts.output_path.clone(),
+                &full_formats,
+                opts.template_dir.as_deref(),
+                opts.quiet,
+                opts.open,
+            )?;
+        }
+
+        report_paths.extend(generate_report(
+            &build_report(&shown_findings, omitted)?,
+            &shown_findings,
             opts.output_path,
-            &opts.output_format,
-        )?;
+            &truncated_formats,
+            opts.template_dir.as_deref(),
+            opts.quiet,
+            opts.open,
+        )?);
+
+        let result = check_findings(&denied_findings, new_findings, changed_findings);
+
+        if let Some(hook) = &opts.post_hook {
+            let exit_status = if result.is_ok() { 0 } else { 1 };
+            if let Err(err) = run_post_hook(hook, &report_paths, exit_status) {
+                // The run's own failure is the one worth returning
+                if result.is_ok() {
+                    return Err(err);
+                }
+                print_error(&format!("{:#}", err));
+            }
+        }
+
+        result?;
+    }
+
+    Ok(())
+}
+
+fn check_findings(
+    denied_findings: &[String],
+    new_findings: usize,
+    changed_findings: Option<(String, usize)>,
+) -> Result<()> {
+    if !denied_findings.is_empty() {
+        let mut ids: Vec<&String> = denied_findings.iter().collect();
+        ids.sort();
+        ids.dedup();
+        bail!(
+            "Found {} findings of denied detectors: {}",
+            denied_findings.len(),
+            ids.iter()
+                .map(|id| id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    if new_findings > 0 {
+        bail!(
+            "Found {} new findings compared to the previous report.",
+            new_findings
+        );
+    }
+
+    if let Some((since, count)) = changed_findings.filter(|(_, count)| *count > 0) {
+        bail!("Found {} findings in files changed since {}.", count, since);
     }
 
     Ok(())
@@ -605,6 +2180,22 @@ fn run_dylint(
     if !inside_vscode {
         args.push("--message-format=json".to_string());
     }
+    if let Some(jobs) = opts.jobs {
+        args.push(format!("--jobs={jobs}"));
+    }
+    for package in opts.package.iter() {
+        args.push(format!("--package={package}"));
+    }
+    // Findings only come from the targets cargo checks, so no filtering is needed afterwards
+    let targets = [
+        (opts.lib, "--lib"),
+        (opts.bins, "--bins"),
+        (opts.tests, "--tests"),
+        (opts.examples, "--examples"),
+    ];
+    for (_, flag) in targets.iter().filter(|(selected, _)| *selected) {
+        args.push(flag.to_string());
+    }
 
     let check_opts = Check {
         lib_sel: LibrarySelection {
@@ -637,22 +2228,52 @@ fn run_dylint(
 
 #[tracing::instrument(name = "GENERATE REPORT", skip_all)]
 fn generate_report(
+    report: &Report,
     findings: &Vec<Value>,
-    crates: &HashMap<String, bool>,
-    project_info: ProjectInfo,
-    detectors_info: &HashMap<String, LintInfo>,
     output_path: Option<PathBuf>,
     output_format: &[OutputFormat],
-) -> Result<()> {
-    let report = RawReport::generate_report(findings, crates, &project_info, detectors_info)?;
-
+    template_dir: Option<&Path>,
+    quiet: bool,
+    open: bool,
+) -> Result<Vec<PathBuf>> {
     tracing::trace!(?output_format, "Output format");
     tracing::trace!(?report, "Report");
 
-    for format in output_format.iter() {
-        let path = report.write_out(findings, output_path.clone(), format)?;
+    let output_path = output_path.map(|path| expand_env_vars(&path)).transpose()?;
+
+    // A format listed twice would race on its own file
+    let formats: Vec<&OutputFormat> = output_format
+        .iter()
+        .enumerate()
+        .filter(|(i, format)| !output_format[..*i].contains(format))
+        .map(|(_, format)| format)
+        .collect();
+
+    // Formats are independent, so they are generated in parallel from the same report
+    let paths = std::thread::scope(|scope| {
+        let handles: Vec<_> = formats
+            .iter()
+            .map(|format| {
+                let output_path = output_path.clone();
+                scope.spawn(move || report.write_out(findings, output_path, format, template_dir))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .map_err(|_| anyhow!("Report generation panicked"))?
+            })
+            .collect::<Result<Vec<_>>>()
+    })?;
 
-        if let Some(path) = path {
+    let mut generated = Vec::new();
+    for (format, path) in formats.into_iter().zip(paths) {
+        let Some(path) = path else {
+            continue;
+        };
+        if !quiet {
             let path = path
                 .to_str()
                 .with_context(|| "Path conversion to string failed")?;
@@ -663,7 +2284,23 @@ fn generate_report(
                 .print();
             println!("{string}");
         }
+        if open {
+            open_report(&path, format)?;
+        }
+        generated.push(path);
     }
 
-    Ok(())
+    Ok(generated)
+}
+
+fn open_report(path: &Path, format: &OutputFormat) -> Result<()> {
+    let result = match format {
+        OutputFormat::Html => webbrowser::open(
+            path.to_str()
+                .with_context(|| "Path conversion to string failed")?,
+        )
+        .map_err(anyhow::Error::from),
+        _ => opener::open(path).map_err(anyhow::Error::from),
+    };
+    result.with_context(|| format!("Failed to open {}", path.display()))
 }
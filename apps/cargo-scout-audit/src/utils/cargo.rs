@@ -51,3 +51,51 @@ fn cargo(subcommand: &str, verb: &str, description: &str, quiet: bool, bc: &Bloc
     }
     command
 }
+
+/// The `--frozen`, `--locked` and `--offline` flags forwarded to cargo, which every cargo
+/// invocation scout makes has to honor for a reproducible run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LockFlags {
+    pub frozen: bool,
+    pub locked: bool,
+    pub offline: bool,
+}
+
+impl LockFlags {
+    /// Reads the flags from the arguments passed through to cargo, up to a `--` separator.
+    pub fn from_args(args: &[String]) -> Self {
+        let args = args.iter().take_while(|arg| *arg != "--");
+        let mut flags = LockFlags::default();
+        for arg in args {
+            match arg.as_str() {
+                "--frozen" => flags.frozen = true,
+                "--locked" => flags.locked = true,
+                "--offline" => flags.offline = true,
+                _ => {}
+            }
+        }
+        flags
+    }
+
+    /// Whether cargo must stay off the network. `--frozen` implies `--offline`.
+    pub fn offline(&self) -> bool {
+        self.frozen || self.offline
+    }
+
+    /// Whether `Cargo.lock` must be left untouched. `--frozen` implies `--locked`.
+    pub fn locked(&self) -> bool {
+        self.frozen || self.locked
+    }
+
+    pub fn args(&self) -> Vec<String> {
+        [
+            (self.frozen, "--frozen"),
+            (self.locked, "--locked"),
+            (self.offline, "--offline"),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, flag)| flag.to_string())
+        .collect()
+    }
+}
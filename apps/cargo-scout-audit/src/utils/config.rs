@@ -1,9 +1,12 @@
-use super::print::print_warning;
-use crate::scout::blockchain::BlockChain;
-use anyhow::{Context, Result};
+use super::{paths, print::print_warning};
+use crate::{
+    output::{report::Severity, severity_map::SeverityMap},
+    scout::blockchain::BlockChain,
+};
+use anyhow::{bail, Context, Result};
 use serde_json::{json, Value};
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fs::{self, File},
     io::{self, Read, Write},
     path::{Path, PathBuf},
@@ -61,29 +64,77 @@ fn sync_config_with_detectors(config: &mut Value, detector_names: &[String]) ->
 
     // Update and sort other profiles
     for (profile, detectors) in config.as_object_mut().unwrap() {
-        if profile != "default" {
-            let profile_detectors = detectors
-                .as_array_mut()
-                .with_context(|| format!("Profile '{}' is not an array", profile))?;
-
-            profile_detectors.retain(|d| {
-                let keep = available_detectors.contains(d.as_str().unwrap_or(""));
-                if !keep {
-                    print_warning(&format!(
-                        "Obsolete detector removed from profile '{}': {}",
-                        profile, d,
-                    ));
+        if profile == "default" {
+            continue;
+        }
+        let warn_obsolete = |detector: &str| {
+            print_warning(&format!(
+                "Obsolete detector removed from profile '{}': {}",
+                profile, detector,
+            ));
+        };
+
+        match detectors {
+            Value::Array(profile_detectors) => {
+                profile_detectors.retain(|d| {
+                    let keep = available_detectors.contains(d.as_str().unwrap_or(""));
+                    if !keep {
+                        warn_obsolete(&d.to_string());
+                    }
+                    keep
+                });
+
+                sort_detectors(profile_detectors);
+            }
+            // Generated profiles list every detector, so new ones are added disabled
+            Value::Object(profile_detectors) => {
+                profile_detectors.retain(|d, _| {
+                    let keep = available_detectors.contains(d);
+                    if !keep {
+                        warn_obsolete(d);
+                    }
+                    keep
+                });
+                for detector in available_detectors.iter() {
+                    if !profile_detectors.contains_key(detector) {
+                        profile_detectors.insert(detector.clone(), json!({ "enabled": false }));
+                    }
                 }
-                keep
-            });
-
-            sort_detectors(profile_detectors);
+            }
+            _ => bail!(invalid_profile_message(profile)),
         }
     }
 
     Ok(())
 }
 
+fn invalid_profile_message(profile: &str) -> String {
+    format!(
+        "Profile '{}' must be a list of detectors or a map of detectors to {{ \"enabled\": true/false }}",
+        profile
+    )
+}
+
+// A profile is either a list of detectors, or a map of every detector to `{ "enabled": bool }`.
+fn parse_profile(profile: &str, detectors: &Value) -> Result<Vec<String>> {
+    match detectors {
+        Value::Array(detectors) => Ok(detectors
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect()),
+        Value::Object(detectors) => Ok(detectors
+            .iter()
+            .filter(|(_, v)| {
+                v.get("enabled")
+                    .and_then(Value::as_bool)
+                    .unwrap_or_default()
+            })
+            .map(|(detector, _)| detector.clone())
+            .collect()),
+        _ => bail!(invalid_profile_message(profile)),
+    }
+}
+
 fn sort_detectors(detectors: &mut [Value]) {
     detectors.sort_by(|a, b| {
         let a_str = a.as_str().unwrap_or("");
@@ -92,6 +143,25 @@ fn sort_detectors(detectors: &mut [Value]) {
     });
 }
 
+// Returns every profile in the configuration with the detectors it enables, `default` first.
+pub fn get_profiles(
+    blockchain: BlockChain,
+    detector_names: &[String],
+) -> Result<Vec<(String, Vec<String>)>> {
+    let (config, _) = open_config_or_default(blockchain, detector_names)?;
+    let profiles = config
+        .as_object()
+        .with_context(|| "Configuration file is not a JSON object")?;
+
+    let mut ret = profiles
+        .iter()
+        .map(|(profile, detectors)| Ok((profile.clone(), parse_profile(profile, detectors)?)))
+        .collect::<Result<Vec<(String, Vec<String>)>>>()?;
+
+    ret.sort_by(|(a, _), (b, _)| (a != "default", a).cmp(&(b != "default", b)));
+    Ok(ret)
+}
+
 fn open_config_or_default(bc: BlockChain, detectors: &[String]) -> Result<(Value, PathBuf)> {
     let config_file_path = get_config_file_path(bc)?;
 
@@ -114,10 +184,7 @@ fn open_config_or_default(bc: BlockChain, detectors: &[String]) -> Result<(Value
 }
 
 fn get_config_file_path(bc: BlockChain) -> Result<PathBuf> {
-    let base_path =
-        std::env::var("HOME").with_context(|| "Failed to get HOME environment variable")?;
-
-    let config_path = PathBuf::from(base_path).join(".config/scout");
+    let config_path = paths::config_dir()?;
 
     fs::create_dir_all(&config_path)
         .with_context(|| format!("Failed to create config directory: {:?}", config_path))?;
@@ -126,6 +193,9 @@ fn get_config_file_path(bc: BlockChain) -> Result<PathBuf> {
         BlockChain::Ink => "ink-config.json",
         BlockChain::Soroban => "soroban-config.json",
         BlockChain::SubstratePallet => "substrate-pallet-config.json",
+        BlockChain::Near => "near-config.json",
+        BlockChain::Solana => "solana-config.json",
+        BlockChain::MultiversX => "multiversx-config.json",
     });
 
     Ok(file_path)
@@ -167,26 +237,13 @@ pub fn profile_enabled_detectors(
         .filter_map(|v| v.as_str().map(String::from))
         .collect();
 
-    let profile_detectors = match config.get(profile).and_then(Value::as_array) {
-        Some(detectors) => detectors,
-        None => {
-            print_warning(&format!(
-                "Profile '{}' does not exist, creating it with default detectors",
-                profile
-            ));
-            create_profile(
-                config_path,
-                &default_detectors.iter().cloned().collect::<Vec<_>>(),
-                profile,
-            )
-            .with_context(|| format!("Failed to create profile '{}'", profile))?;
-            config.get("default").and_then(Value::as_array).unwrap()
-        }
+    let profile_detectors = match config.get(profile) {
+        Some(detectors) => parse_profile(profile, detectors)?,
+        None => bail!(unknown_profile_message(config, profile, config_path)),
     };
 
     let enabled_detectors: Vec<String> = profile_detectors
-        .iter()
-        .filter_map(|v| v.as_str().map(String::from))
+        .into_iter()
         .filter(|detector| {
             default_detectors.contains(detector) && detector_names.contains(detector)
         })
@@ -202,24 +259,95 @@ pub fn profile_enabled_detectors(
     }
 }
 
-fn create_profile(file_path: &Path, detectors: &[String], profile: &str) -> Result<()> {
-    let existing_profiles = read_file_to_string(file_path)
-        .with_context(|| "Failed to read config file")?
-        .parse::<Value>()
-        .with_context(|| "Failed to parse JSON config")?;
+// Adds a profile listing every detector as enabled, for the user to switch off what they don't need.
+pub fn generate_profile(
+    blockchain: BlockChain,
+    profile: &str,
+    detector_names: &[String],
+) -> Result<PathBuf> {
+    let (mut config, config_path) = open_config_and_sync_detectors(blockchain, detector_names)?;
+    let profiles = config
+        .as_object_mut()
+        .with_context(|| "Configuration file is not a JSON object")?;
+
+    if profiles.contains_key(profile) {
+        bail!(
+            "Profile '{}' already exists in the configuration file at: {}",
+            profile,
+            config_path.display()
+        );
+    }
 
-    let mut new_profiles = existing_profiles.clone();
-    new_profiles[profile] = detectors.iter().map(|d| Value::String(d.clone())).collect();
+    let detectors: serde_json::Map<String, Value> = detector_names
+        .iter()
+        .map(|detector| (detector.clone(), json!({ "enabled": true })))
+        .collect();
+    profiles.insert(profile.to_string(), Value::Object(detectors));
 
-    let config_str = serde_json::to_string_pretty(&new_profiles)
-        .with_context(|| "Failed to serialize config to JSON string")?;
+    save_config(&config, &config_path)?;
 
-    File::create(file_path)
-        .with_context(|| format!("Failed to create file: {:?}", file_path))?
-        .write_all(config_str.as_bytes())
-        .with_context(|| "Failed to write config to file")?;
+    Ok(config_path)
+}
 
-    Ok(())
+// Severities set by a map profile, as in `{ "detector": { "enabled": true, "severity": "Critical" } }`.
+// Custom labels of the severity map are accepted too.
+pub fn profile_severity_overrides(
+    config: &Value,
+    profile: &str,
+    severity_map: &SeverityMap,
+) -> Result<HashMap<String, Severity>> {
+    let Some(Value::Object(detectors)) = config.get(profile) else {
+        return Ok(HashMap::new());
+    };
+
+    let mut overrides = HashMap::new();
+    for (detector, settings) in detectors.iter() {
+        let Some(severity) = settings.get("severity") else {
+            continue;
+        };
+        let severity = severity
+            .as_str()
+            .and_then(|severity| severity_map.severity(severity))
+            .with_context(|| {
+                format!(
+                    "Invalid severity {} for detector '{}' in profile '{}'. Expected one of: {}",
+                    severity,
+                    detector,
+                    profile,
+                    Severity::labels()
+                )
+            })?;
+        overrides.insert(detector.clone(), severity);
+    }
+
+    Ok(overrides)
+}
+
+fn unknown_profile_message(config: &Value, profile: &str, config_path: &Path) -> String {
+    let available: Vec<&String> = config
+        .as_object()
+        .map(|profiles| profiles.keys().collect())
+        .unwrap_or_default();
+
+    let suggestion = available
+        .iter()
+        .map(|name| (strsim::levenshtein(profile, name), name))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| format!(" Did you mean '{}'?", name))
+        .unwrap_or_default();
+
+    format!(
+        "Profile '{}' does not exist.{}\n\n     Available profiles: {}\n     Profiles are defined in the configuration file at: {}",
+        profile,
+        suggestion,
+        available
+            .iter()
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+        config_path.display()
+    )
 }
 
 fn save_config(config: &Value, config_path: &Path) -> Result<()> {
@@ -1,5 +1,7 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
+use super::{detectors_info::LintInfo, glob::glob_regex, print::print_warning};
+use crate::output::{report::SkippedDetector, severity_map::SeverityMap};
 use anyhow::bail;
 use anyhow::Result;
 
@@ -12,46 +14,253 @@ fn parse_detectors(detectors: &str) -> Vec<String> {
         .collect()
 }
 
-pub fn get_filtered_detectors(filter: &str, detectors_names: &[String]) -> Result<Vec<String>> {
+// Filters that select nothing are warnings, or errors when running in strict mode.
+fn report_unmatched(message: String, strict: bool) -> Result<()> {
+    if strict {
+        bail!(message);
+    }
+    print_warning(&message);
+    Ok(())
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+// Expands a `*`/`?` glob into the detectors it matches, keeping their original order.
+fn glob_matches(pattern: &str, detectors_names: &[String]) -> Vec<String> {
+    let Ok(regex) = glob_regex(pattern) else {
+        return vec![];
+    };
+
+    detectors_names
+        .iter()
+        .filter(|name| regex.is_match(name))
+        .cloned()
+        .collect()
+}
+
+pub fn get_filtered_detectors(
+    filter: &str,
+    detectors_names: &[String],
+    strict: bool,
+) -> Result<Vec<String>> {
     let detectors_set: HashSet<_> = detectors_names.iter().collect();
     let parsed_detectors = parse_detectors(filter);
 
-    parsed_detectors
-        .iter()
-        .try_fold(Vec::new(), |mut acc, detector| {
-            if detectors_set.contains(detector) {
-                acc.push(detector.clone());
-                Ok(acc)
-            } else {
-                bail!("The detector '{}' does not exist. Use the `--list` flag to see available detectors.", detector)
+    let mut ret = Vec::<String>::new();
+    for detector in parsed_detectors.iter() {
+        let matches = if is_glob(detector) {
+            let matches = glob_matches(detector, detectors_names);
+            if matches.is_empty() {
+                report_unmatched(
+                    format!("The pattern '{}' did not match any detector.", detector),
+                    strict,
+                )?;
+            }
+            matches
+        } else if detectors_set.contains(detector) {
+            vec![detector.clone()]
+        } else {
+            bail!("The detector '{}' does not exist. Use the `--list` flag to see available detectors.", detector)
+        };
+
+        for detector in matches {
+            if !ret.contains(&detector) {
+                ret.push(detector);
             }
-        })
+        }
+    }
+
+    Ok(ret)
 }
 
-pub fn get_excluded_detectors(excluded: &str, detectors_names: &[String]) -> Vec<String> {
-    let excluded_set: HashSet<_> = parse_detectors(excluded).into_iter().collect();
+pub fn get_excluded_detectors(
+    excluded: &str,
+    detectors_names: &[String],
+    strict: bool,
+) -> Result<Vec<String>> {
+    let mut excluded_set = HashSet::<String>::new();
+    for pattern in parse_detectors(excluded) {
+        if is_glob(&pattern) {
+            let matches = glob_matches(&pattern, detectors_names);
+            if matches.is_empty() {
+                report_unmatched(
+                    format!("The pattern '{}' did not match any detector.", pattern),
+                    strict,
+                )?;
+            }
+            excluded_set.extend(matches);
+        } else {
+            if !detectors_names.contains(&pattern) {
+                report_unmatched(
+                    format!("The excluded detector '{}' does not exist. Use the `--list` flag to see available detectors.", pattern),
+                    strict,
+                )?;
+            }
+            excluded_set.insert(pattern);
+        }
+    }
 
-    detectors_names
+    Ok(detectors_names
         .iter()
         .filter(|&name| !excluded_set.contains(name))
         .cloned()
-        .collect()
+        .collect())
 }
 
-pub fn list_detectors(detectors_names: &[String]) {
+// With a profile's `enabled` detectors, every detector is marked as enabled or disabled.
+pub fn list_detectors(detectors_names: &[String], enabled: Option<&[String]>) -> String {
     let separator = "─".repeat(48);
-    let upper_border = format!("┌{}┐", separator);
-    let lower_border = format!("└{}┘", separator);
     let empty_line = format!("│{:48}│", "");
 
-    println!("{}", upper_border);
-    println!("│{:^47}│", "🔍 Available detectors:");
-    println!("{}", empty_line);
+    let mut lines = vec![
+        format!("┌{}┐", separator),
+        format!("│{:^47}│", "🔍 Available detectors:"),
+        empty_line.clone(),
+    ];
 
     for (index, detector_name) in detectors_names.iter().enumerate() {
-        println!("│ {:>2}. {:<43}│", index + 1, detector_name);
+        lines.push(match enabled {
+            Some(enabled) => {
+                let state = if enabled.contains(detector_name) {
+                    "enabled"
+                } else {
+                    "disabled"
+                };
+                format!("│ {:>2}. {:<34}{:<9}│", index + 1, detector_name, state)
+            }
+            None => format!("│ {:>2}. {:<43}│", index + 1, detector_name),
+        });
+    }
+
+    lines.push(empty_line);
+    lines.push(format!("└{}┘", separator));
+    lines.join("\n")
+}
+
+pub fn list_profiles(profiles: &[(String, Vec<String>)]) -> String {
+    let mut lines = Vec::new();
+    for (profile, detectors) in profiles.iter() {
+        lines.push(format!("{} ({} detectors)", profile, detectors.len()));
+        for detector in detectors.iter() {
+            lines.push(format!("  - {}", detector));
+        }
+    }
+    lines.join("\n")
+}
+
+pub fn list_categories(detectors_info: &HashMap<String, LintInfo>) -> String {
+    let mut categories = BTreeMap::<&str, Vec<&str>>::new();
+    for info in detectors_info.values() {
+        categories
+            .entry(info.vulnerability_class.as_str())
+            .or_default()
+            .push(info.id.as_str());
+    }
+
+    let mut lines = Vec::new();
+    for (category, detectors) in categories.iter_mut() {
+        detectors.sort_unstable();
+        lines.push(format!("{} ({} detectors)", category, detectors.len()));
+        for detector in detectors.iter() {
+            lines.push(format!("  - {}", detector));
+        }
+    }
+    lines.join("\n")
+}
+
+fn lint_info_problems(info: &LintInfo, severity_map: &SeverityMap) -> Vec<String> {
+    let mut problems: Vec<String> = [
+        ("id", &info.id),
+        ("name", &info.name),
+        ("short_message", &info.short_message),
+        ("long_message", &info.long_message),
+        ("severity", &info.severity),
+        ("help", &info.help),
+        ("vulnerability_class", &info.vulnerability_class),
+    ]
+    .iter()
+    .filter(|(_, value)| value.trim().is_empty())
+    .map(|(field, _)| format!("empty `{}`", field))
+    .collect();
+
+    if !info.severity.trim().is_empty() && severity_map.severity(&info.severity).is_none() {
+        problems.push(format!("unknown severity '{}'", info.severity));
+    }
+    problems
+}
+
+/// Lists whether each detector loaded with a well-formed `lint_info`, failing with the list if
+/// any did not.
+pub fn self_test_detectors(
+    detectors_info: &HashMap<String, LintInfo>,
+    skipped: &[SkippedDetector],
+    severity_map: &SeverityMap,
+) -> Result<String> {
+    let mut lines: Vec<String> = skipped
+        .iter()
+        .map(|skipped| format!("FAIL {}: {}", skipped.path.display(), skipped.error))
+        .collect();
+    let mut failed = skipped.len();
+
+    let mut ids: Vec<&String> = detectors_info.keys().collect();
+    ids.sort_unstable();
+    for id in ids {
+        let problems = lint_info_problems(&detectors_info[id], severity_map);
+        if problems.is_empty() {
+            lines.push(format!("PASS {}", id));
+        } else {
+            failed += 1;
+            lines.push(format!("FAIL {}: {}", id, problems.join(", ")));
+        }
+    }
+
+    let total = detectors_info.len() + skipped.len();
+    if failed > 0 {
+        bail!(
+            "{} of {} detectors failed the self-test.\n\n{}",
+            failed,
+            total,
+            lines.join("\n")
+        );
+    }
+    lines.push(format!("All {} detectors passed the self-test.", total));
+    Ok(lines.join("\n"))
+}
+
+pub fn explain_detector(id: &str, detectors_info: &HashMap<String, LintInfo>) -> Result<String> {
+    let id = id.trim().to_lowercase().replace('-', "_");
+    let Some(info) = detectors_info.get(&id) else {
+        let suggestion = detectors_info
+            .keys()
+            .map(|name| (strsim::levenshtein(&id, name), name))
+            .filter(|(distance, _)| *distance <= 3)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, name)| format!(" Did you mean '{}'?", name))
+            .unwrap_or_default();
+        bail!(
+            "The detector '{}' does not exist.{} Use the `--list` flag to see available detectors.",
+            id,
+            suggestion
+        );
+    };
+
+    let mut lines = vec![
+        format!("{} ({})", info.name, info.id),
+        String::new(),
+        format!("Severity:            {}", info.severity),
+        format!("Vulnerability class: {}", info.vulnerability_class),
+        String::new(),
+        info.short_message.clone(),
+        String::new(),
+        info.long_message.clone(),
+        String::new(),
+        format!("Help: {}", info.help),
+    ];
+    if let Some(doc_url) = &info.doc_url {
+        lines.push(format!("Documentation: {}", doc_url));
     }
 
-    println!("{}", empty_line);
-    println!("{}", lower_border);
+    Ok(lines.join("\n"))
 }
@@ -1,11 +1,28 @@
-use anyhow::{anyhow, Result};
+use super::paths;
+use crate::{
+    output::{report::SkippedDetector, utils::write_to_file},
+    scout::blockchain::BlockChain,
+};
+use anyhow::{anyhow, bail, Result};
+use clap::ValueEnum;
 use libloading::{Library, Symbol};
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::{collections::HashMap, ffi::CString, path::PathBuf};
+use std::{
+    collections::HashMap,
+    ffi::CString,
+    fs, mem,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
 
+// Laid out as scout-audit-dylint-linting's `LintInfo`, which detectors fill in
+#[repr(C)]
 #[derive(Default, Debug, Clone)]
 pub struct RawLintInfo {
+    // The bytes a detector may fill in, and then the bytes it did
+    pub size: usize,
     pub id: CString,
     pub name: CString,
     pub short_message: CString,
@@ -13,9 +30,52 @@ pub struct RawLintInfo {
     pub severity: CString,
     pub help: CString,
     pub vulnerability_class: CString,
+    pub doc_url: CString,
+    // Added later, so detectors built before them don't fill them in
+    pub confidence: CString,
+    pub cwe: CString,
+    pub swc: CString,
 }
 
-#[derive(Default, Debug, Clone, Serialize)]
+/// How likely the findings of a detector are to be true positives. Declared from the least to
+/// the most confident, so `--min-confidence` keeps the findings at or above it.
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    Low,
+    Medium,
+    #[default]
+    High,
+}
+
+impl Confidence {
+    fn parse(label: &str) -> Result<Self> {
+        match label.trim().to_lowercase().as_str() {
+            "" | "high" => Ok(Confidence::High),
+            "medium" => Ok(Confidence::Medium),
+            "low" => Ok(Confidence::Low),
+            _ => bail!(
+                "Unknown confidence '{}', expected high, medium or low",
+                label
+            ),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LintInfo {
     pub id: String,
     pub name: String,
@@ -24,6 +84,14 @@ pub struct LintInfo {
     pub severity: String,
     pub help: String,
     pub vulnerability_class: String,
+    pub doc_url: Option<String>,
+    #[serde(default)]
+    pub confidence: Confidence,
+    // Weakness the detector looks for in the CWE and SWC catalogs, when it maps to one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwe: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swc: Option<String>,
 }
 
 pub struct CustomLint<'lib> {
@@ -43,6 +111,10 @@ impl TryFrom<&RawLintInfo> for LintInfo {
             severity: info.severity.to_str()?.to_string(),
             help: info.help.to_str()?.to_string(),
             vulnerability_class: info.vulnerability_class.to_str()?.to_string(),
+            doc_url: Some(info.doc_url.to_str()?.to_string()).filter(|url| !url.is_empty()),
+            confidence: Confidence::parse(info.confidence.to_str()?)?,
+            cwe: Some(info.cwe.to_str()?.trim().to_string()).filter(|id| !id.is_empty()),
+            swc: Some(info.swc.to_str()?.trim().to_string()).filter(|id| !id.is_empty()),
         })
     }
 }
@@ -64,52 +136,208 @@ impl<'lib> CustomLint<'lib> {
 
 type LintInfoFunc = unsafe fn(info: &mut RawLintInfo);
 type CustomLintFunc = unsafe fn();
+type DetectorsInfo<'a> = (
+    HashMap<String, LintInfo>,
+    HashMap<String, CustomLint<'a>>,
+    Vec<SkippedDetector>,
+);
+
+fn load_detector<'a>(
+    detector_path: &'a PathBuf,
+    blockchain: &BlockChain,
+) -> Result<(LintInfo, Option<CustomLint<'a>>)> {
+    let lib = unsafe {
+        Library::new(detector_path)
+            .map_err(|e| anyhow!("Failed to load library {}: {}", detector_path.display(), e))?
+    };
+    let lib = Arc::new(lib);
+
+    let lint_info_func: Symbol<LintInfoFunc> = unsafe {
+        lib.get(b"lint_info").map_err(|e| {
+            anyhow!(
+                "Failed to get lint_info function from {}: {}",
+                detector_path.display(),
+                e
+            )
+        })?
+    };
+    let custom_detector_func: Option<Symbol<CustomLintFunc>> =
+        unsafe { (*Arc::as_ptr(&lib)).get(b"custom_detector").ok() };
+
+    let mut raw_info = RawLintInfo {
+        size: mem::size_of::<RawLintInfo>(),
+        ..RawLintInfo::default()
+    };
+    unsafe { lint_info_func(&mut raw_info) };
+    // Every detector fills in the fields up to `vulnerability_class`
+    if raw_info.size < mem::offset_of!(RawLintInfo, doc_url) {
+        bail!(
+            "The lint info of {} is incompatible with this version of scout. Rebuild the detector against the current scout-audit-dylint-linting.",
+            detector_path.display()
+        );
+    }
+
+    let mut lint_info = LintInfo::try_from(&raw_info).map_err(|e| {
+        anyhow!(
+            "Failed to convert RawLintInfo from {}: {}",
+            detector_path.display(),
+            e
+        )
+    })?;
+
+    // Fall back to the detector folder in the detectors repository
+    if lint_info.doc_url.is_none() {
+        lint_info.doc_url = Some(blockchain.get_detector_doc_url(&lint_info.id));
+    }
+
+    let custom_detector =
+        custom_detector_func.map(|custom_detector_func| CustomLint::new(lib, custom_detector_func));
+
+    Ok((lint_info, custom_detector))
+}
 
+// Detectors that fail to load are skipped and reported instead of aborting the run.
 #[tracing::instrument(level = "debug", skip_all)]
-pub fn get_detectors_info(
+pub fn get_detectors_info<'a>(
+    detectors_paths: &'a [PathBuf],
+    blockchain: &BlockChain,
+) -> Result<DetectorsInfo<'a>> {
+    Ok(collect_detectors_info(detectors_paths, |path| {
+        load_detector(path, blockchain)
+    }))
+}
+
+/// Like `get_detectors_info`, but the lint info of libraries that didn't change since the last
+/// run comes from a cache instead of loading them. Custom detectors aren't available, so it's
+/// only meant for listing the detectors' metadata.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn get_cached_detectors_info(
     detectors_paths: &[PathBuf],
-) -> Result<(HashMap<String, LintInfo>, HashMap<String, CustomLint<'_>>)> {
+    blockchain: &BlockChain,
+) -> Result<(HashMap<String, LintInfo>, Vec<SkippedDetector>)> {
+    let mut cache = read_lint_info_cache();
+    let mut updated = false;
+
+    let (lint_store, _, skipped) = collect_detectors_info(detectors_paths, |path| {
+        let key = path.to_string_lossy().to_string();
+        let stamp = LibraryStamp::of(path);
+        if let Some(entry) = cache
+            .get(&key)
+            .filter(|entry| Some(&entry.stamp) == stamp.as_ref())
+        {
+            return Ok((entry.info.clone(), None::<()>));
+        }
+
+        let (info, _) = load_detector(path, blockchain)?;
+        if let Some(stamp) = stamp {
+            cache.insert(
+                key,
+                CachedLintInfo {
+                    stamp,
+                    info: info.clone(),
+                },
+            );
+            updated = true;
+        }
+        Ok((info, None))
+    });
+
+    if updated {
+        write_lint_info_cache(&cache);
+    }
+
+    Ok((lint_store, skipped))
+}
+
+// A library is assumed unchanged while its size and modification time are
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct LibraryStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl LibraryStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(LibraryStamp {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedLintInfo {
+    stamp: LibraryStamp,
+    info: LintInfo,
+}
+
+fn lint_info_cache_path() -> Option<PathBuf> {
+    paths::scout_home().map(|home| home.join("detectors-info.json"))
+}
+
+fn read_lint_info_cache() -> HashMap<String, CachedLintInfo> {
+    lint_info_cache_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+// A cache that cannot be written only costs loading the libraries again next time.
+fn write_lint_info_cache(cache: &HashMap<String, CachedLintInfo>) {
+    let Some(path) = lint_info_cache_path() else {
+        return;
+    };
+    if let Ok(content) = serde_json::to_string(cache) {
+        let _ = write_to_file(&path, content.as_bytes());
+    }
+}
+
+fn collect_detectors_info<'a, T>(
+    detectors_paths: &'a [PathBuf],
+    mut load: impl FnMut(&'a PathBuf) -> Result<(LintInfo, Option<T>)>,
+) -> (
+    HashMap<String, LintInfo>,
+    HashMap<String, T>,
+    Vec<SkippedDetector>,
+) {
     let mut lint_store = HashMap::new();
     let mut custom_dectectors = HashMap::new();
+    let mut skipped = Vec::new();
+    let mut loaded_from = HashMap::<String, &PathBuf>::new();
 
     for detector_path in detectors_paths {
-        let lib = unsafe {
-            Library::new(detector_path)
-                .map_err(|e| anyhow!("Failed to load library {}: {}", detector_path.display(), e))?
-        };
-        let lib = Arc::new(lib);
-
-        let lint_info_func: Symbol<LintInfoFunc> = unsafe {
-            lib.get(b"lint_info").map_err(|e| {
-                anyhow!(
-                    "Failed to get lint_info function from {}: {}",
-                    detector_path.display(),
-                    e
-                )
-            })?
-        };
-        let custom_detector_func: Option<Symbol<CustomLintFunc>> =
-            unsafe { (*Arc::as_ptr(&lib)).get(b"custom_detector").ok() };
-
-        let mut raw_info = RawLintInfo::default();
-        unsafe { lint_info_func(&mut raw_info) };
-
-        let lint_info = LintInfo::try_from(&raw_info).map_err(|e| {
-            anyhow!(
-                "Failed to convert RawLintInfo from {}: {}",
-                detector_path.display(),
-                e
-            )
-        })?;
+        match load(detector_path) {
+            Ok((lint_info, custom_detector)) => {
+                let id = lint_info.id.clone();
 
-        let id = lint_info.id.clone();
+                // The first library keeps the id; loading both would report its findings twice
+                if let Some(first) = loaded_from.get(&id) {
+                    skipped.push(SkippedDetector {
+                        path: detector_path.clone(),
+                        error: format!(
+                            "The lint id '{}' of {} is already used by {}",
+                            id,
+                            detector_path.display(),
+                            first.display()
+                        ),
+                    });
+                    continue;
+                }
+                loaded_from.insert(id.clone(), detector_path);
 
-        lint_store.insert(id.clone(), lint_info);
+                lint_store.insert(id.clone(), lint_info);
 
-        if let Some(custom_detector_func) = custom_detector_func {
-            custom_dectectors.insert(id, CustomLint::new(lib, custom_detector_func));
+                if let Some(custom_detector) = custom_detector {
+                    custom_dectectors.insert(id, custom_detector);
+                }
+            }
+            Err(e) => skipped.push(SkippedDetector {
+                path: detector_path.clone(),
+                error: e.to_string(),
+            }),
         }
     }
 
-    Ok((lint_store, custom_dectectors))
+    (lint_store, custom_dectectors, skipped)
 }
@@ -1,3 +1,5 @@
+use std::ffi::{OsStr, OsString};
+
 macro_rules! declare_const {
     ($var: ident) => {
         pub const $var: &str = stringify!($var);
@@ -6,6 +8,7 @@ macro_rules! declare_const {
 
 declare_const!(CARGO_HOME);
 declare_const!(CARGO_MANIFEST_DIR);
+declare_const!(CARGO_NET_OFFLINE);
 declare_const!(CARGO_PKG_NAME);
 declare_const!(CARGO_TARGET_DIR);
 declare_const!(CARGO_TERM_COLOR);
@@ -26,4 +29,37 @@ declare_const!(RUSTFLAGS);
 declare_const!(RUSTUP_HOME);
 declare_const!(RUSTUP_TOOLCHAIN);
 declare_const!(RUST_BACKTRACE);
+declare_const!(SCOUT_BIND_ADDRESS);
+declare_const!(SCOUT_EXIT_STATUS);
+declare_const!(SCOUT_HOME);
+declare_const!(SCOUT_NIGHTLY_OPTIONS);
+declare_const!(SCOUT_PORT_NUMBER);
+declare_const!(SCOUT_SERVER_ADDRESS);
+declare_const!(SCOUT_SERVER_PORT);
 declare_const!(TARGET);
+
+/// Sets environment variables until dropped, then puts back their previous values. Cargo and
+/// dylint read some settings only from the environment, so a run can't pass them any other way.
+#[derive(Default)]
+pub struct ScopedEnv {
+    previous: Vec<(&'static str, Option<OsString>)>,
+}
+
+impl ScopedEnv {
+    pub fn set(&mut self, key: &'static str, value: impl AsRef<OsStr>) {
+        self.previous.push((key, std::env::var_os(key)));
+        std::env::set_var(key, value);
+    }
+}
+
+impl Drop for ScopedEnv {
+    fn drop(&mut self) {
+        // In reverse, so a variable set twice gets its original value back
+        for (key, value) in self.previous.drain(..).rev() {
+            match value {
+                Some(value) => std::env::set_var(key, value),
+                None => std::env::remove_var(key),
+            }
+        }
+    }
+}
@@ -4,5 +4,8 @@ pub mod config;
 pub mod detectors;
 pub mod detectors_info;
 pub mod env;
+pub mod glob;
+pub mod paths;
 pub mod print;
+pub mod progress;
 pub mod telemetry;
@@ -0,0 +1,44 @@
+use super::env;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+// Root directory for everything scout persists between runs. `SCOUT_HOME` takes precedence over
+// the platform data directory so CI can point it at a writable, cacheable location.
+pub fn scout_home() -> Option<PathBuf> {
+    std::env::var_os(env::SCOUT_HOME)
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            directories::ProjectDirs::from("com", "CoinFabrik", "scout-audit")
+                .map(|dirs| dirs.data_dir().to_path_buf())
+        })
+}
+
+pub fn templates_dir() -> Option<PathBuf> {
+    scout_home().map(|home| home.join("templates"))
+}
+
+// Detectors are built next to the analyzed crate when a target directory is set, so caching it
+// caches them too. `SCOUT_HOME` still wins, as it is set on purpose.
+pub fn libraries_dir(toolchain: &str) -> Option<PathBuf> {
+    let home = std::env::var_os(env::SCOUT_HOME).filter(|home| !home.is_empty());
+    let target_dir = std::env::var_os(env::CARGO_TARGET_DIR).filter(|dir| !dir.is_empty());
+    match (home, target_dir) {
+        (None, Some(target_dir)) => Some(PathBuf::from(target_dir).join("scout")),
+        _ => scout_home(),
+    }
+    .map(|home| home.join("libraries").join(toolchain))
+}
+
+// Configuration keeps living in `~/.config/scout` unless `SCOUT_HOME` is set, so existing
+// profiles are not lost.
+pub fn config_dir() -> Result<PathBuf> {
+    if let Some(home) = std::env::var_os(env::SCOUT_HOME).filter(|home| !home.is_empty()) {
+        return Ok(PathBuf::from(home).join("config"));
+    }
+
+    let base_path =
+        std::env::var("HOME").with_context(|| "Failed to get HOME environment variable")?;
+
+    Ok(PathBuf::from(base_path).join(".config/scout"))
+}
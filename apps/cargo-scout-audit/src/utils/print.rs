@@ -1,11 +1,71 @@
 use colored::Colorize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static COLOR: AtomicBool = AtomicBool::new(true);
+// Whether `colored` was told to ignore the terminal, as it is by `set_color`
+static COLOR_OVERRIDDEN: AtomicBool = AtomicBool::new(false);
+
+// Silences warnings and non-fatal errors, so only the requested report is written. Fatal errors
+// are still printed by the caller.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn set_color(enabled: bool) {
+    COLOR.store(enabled, Ordering::Relaxed);
+    COLOR_OVERRIDDEN.store(true, Ordering::Relaxed);
+    colored::control::set_override(enabled);
+}
+
+/// Applies the quiet and color settings of a run until dropped, then puts back the previous
+/// ones, so running scout from another program leaves that program's settings alone.
+pub struct PrintSettings {
+    quiet: bool,
+    color: bool,
+    color_overridden: bool,
+}
+
+impl PrintSettings {
+    pub fn apply(quiet: bool, color: bool) -> Self {
+        let previous = Self {
+            quiet: QUIET.load(Ordering::Relaxed),
+            color: COLOR.load(Ordering::Relaxed),
+            color_overridden: COLOR_OVERRIDDEN.load(Ordering::Relaxed),
+        };
+        set_quiet(quiet);
+        set_color(color);
+        previous
+    }
+}
+
+impl Drop for PrintSettings {
+    fn drop(&mut self) {
+        set_quiet(self.quiet);
+        if self.color_overridden {
+            set_color(self.color);
+        } else {
+            COLOR.store(self.color, Ordering::Relaxed);
+            COLOR_OVERRIDDEN.store(false, Ordering::Relaxed);
+            colored::control::unset_override();
+        }
+    }
+}
+
+pub fn color_enabled() -> bool {
+    COLOR.load(Ordering::Relaxed)
+}
 
 pub fn print_warning(message: &str) {
-    println!("{}", pretty_warning(message));
+    if !QUIET.load(Ordering::Relaxed) {
+        println!("{}", pretty_warning(message));
+    }
 }
 
 pub fn print_error(message: &str) {
-    println!("{}", pretty_error(message));
+    if !QUIET.load(Ordering::Relaxed) {
+        println!("{}", pretty_error(message));
+    }
 }
 
 pub fn pretty_warning(message: &str) -> String {
@@ -0,0 +1,72 @@
+use indicatif::{ProgressBar, ProgressStyle};
+use std::{
+    fs,
+    io::IsTerminal,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, SystemTime},
+};
+
+// Progress is only drawn when a user is watching: both streams are terminals and the output is
+// not being consumed as JSON (the VSCode extension case).
+pub fn is_enabled(inside_vscode: bool) -> bool {
+    !inside_vscode && std::io::stdout().is_terminal() && std::io::stderr().is_terminal()
+}
+
+fn style(template: &str) -> ProgressStyle {
+    ProgressStyle::with_template(template).unwrap_or_else(|_| ProgressStyle::default_spinner())
+}
+
+pub fn spinner(enabled: bool, message: &str) -> ProgressBar {
+    if !enabled {
+        return ProgressBar::hidden();
+    }
+
+    let bar = ProgressBar::new_spinner().with_style(style("{spinner} {msg} [{elapsed}]"));
+    bar.set_message(message.to_string());
+    bar.enable_steady_tick(Duration::from_millis(100));
+    bar
+}
+
+pub fn counter(enabled: bool, message: &str, total: usize) -> ProgressBar {
+    if !enabled {
+        return ProgressBar::hidden();
+    }
+
+    let bar =
+        ProgressBar::new(total as u64).with_style(style("{spinner} {msg} {pos}/{len} [{elapsed}]"));
+    bar.set_message(message.to_string());
+    bar.enable_steady_tick(Duration::from_millis(100));
+    bar
+}
+
+// Runs `f` while advancing `bar` each time one of `outputs` is written.
+pub fn track_outputs<T>(bar: &ProgressBar, outputs: &[PathBuf], f: impl FnOnce() -> T) -> T {
+    if bar.is_hidden() {
+        return f();
+    }
+
+    let started = SystemTime::now();
+    let done = AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                let built = outputs
+                    .iter()
+                    .filter(|path| {
+                        fs::metadata(path)
+                            .and_then(|metadata| metadata.modified())
+                            .is_ok_and(|modified| modified >= started)
+                    })
+                    .count();
+                bar.set_position(built as u64);
+                std::thread::sleep(Duration::from_millis(200));
+            }
+        });
+
+        let result = f();
+        done.store(true, Ordering::Relaxed);
+        result
+    })
+}
@@ -1,8 +1,34 @@
+use anyhow::{Context, Result};
+use std::{fs::File, path::Path, sync::Mutex};
 pub use tracing;
 use tracing::{subscriber::set_global_default, Subscriber};
 use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
 use tracing_log::LogTracer;
-use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt, EnvFilter, Registry};
+use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt, EnvFilter, Layer, Registry};
+
+/// File receiving the JSON logs of a run, independently of the console output.
+pub struct LogFile {
+    file: File,
+    filter: EnvFilter,
+}
+
+impl LogFile {
+    /// Opens the log file, keeping events and spans at `level` or above.
+    ///
+    /// Processes that continue a run, like the nightly child, append to the parent's log.
+    pub fn open(path: &Path, level: &str, append: bool) -> Result<Self> {
+        let filter =
+            EnvFilter::try_new(level).with_context(|| format!("Invalid log level: {}", level))?;
+        let file = File::options()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)
+            .with_context(|| format!("Failed to open log file: {}", path.display()))?;
+        Ok(Self { file, filter })
+    }
+}
 
 /// Compose multiple layers into a `tracing`'s subscriber.
 ///
@@ -17,6 +43,7 @@ pub fn get_subscriber<Sink>(
     name: String,
     env_filter: String,
     sink: Sink,
+    log_file: Option<LogFile>,
 ) -> impl Subscriber + Send + Sync
 where
     Sink: for<'a> MakeWriter<'a> + Send + Sync + 'static,
@@ -24,12 +51,16 @@ where
     let env_filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(env_filter));
 
-    let formatting_layer = BunyanFormattingLayer::new(name, sink);
+    // Each layer has its own filter, so the log file level doesn't affect the console
+    let formatting_layer = BunyanFormattingLayer::new(name.clone(), sink).with_filter(env_filter);
+    let log_file_layer = log_file.map(|log_file| {
+        BunyanFormattingLayer::new(name, Mutex::new(log_file.file)).with_filter(log_file.filter)
+    });
 
     Registry::default()
-        .with(env_filter)
         .with(JsonStorageLayer)
         .with(formatting_layer)
+        .with(log_file_layer)
 }
 
 /// Register a subscriber as global default to process span data.
@@ -1,9 +1,31 @@
 #[cfg(test)]
 mod tests {
-    use anyhow::{Context, Result};
-    use cargo_scout_audit::startup::{run_scout, OutputFormat, Scout};
+    use anyhow::{bail, Context, Result};
+    use cargo_scout_audit::{
+        output::{
+            report::{ReportStatus, Severity, Vulnerability},
+            severity_map::SeverityMap,
+        },
+        scout::{
+            bundle::Analysis,
+            changed_files::{filter_changed_findings, filter_ignored_paths},
+            diff::diff_findings,
+            findings::{dedup_findings, filter_by_confidence},
+            nightly_runner::nightly_command,
+            single_file::synthesize_crate,
+            suppressions::split_suppressed,
+        },
+        server::{FindingKind, FindingRequest},
+        startup::{analyze, run_scout, OutputFormat, Scout},
+        utils::{
+            cargo::LockFlags,
+            detectors::{get_excluded_detectors, get_filtered_detectors},
+            detectors_info::{Confidence, LintInfo},
+            glob::glob_regex,
+        },
+    };
     use serde_json::Value;
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
     use std::path::Path;
     use std::{fs, path::PathBuf};
 
@@ -136,6 +158,22 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_detectors_self_test() {
+        // Given
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            self_test: true,
+            ..Scout::default()
+        };
+
+        // When
+        let result = run_scout(scout_opts);
+
+        // Then
+        assert!(result.is_ok_and(|findings| findings.is_empty()));
+    }
+
     fn test_output_fn(file: &str, format: OutputFormat) -> Result<()> {
         test_output_format(file, &format)
             .with_context(|| format!("Failed to test {:?} format", &format))?;
@@ -180,6 +218,803 @@ mod tests {
         test_output_fn("report.pdf", OutputFormat::Pdf)
     }
 
+    #[test]
+    fn test_codeclimate_format() -> Result<()> {
+        test_output_fn("codeclimate.json", OutputFormat::CodeClimate)
+    }
+
+    #[test]
+    fn test_json_by_file_format() -> Result<()> {
+        test_output_fn("findings-by-file.json", OutputFormat::JsonByFile)
+    }
+
+    #[test]
+    fn test_toml_format() -> Result<()> {
+        test_output_fn("report.toml", OutputFormat::Toml)
+    }
+
+    #[test]
+    fn test_formats_sharing_a_file_are_rejected() {
+        // Given
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            output_format: vec![OutputFormat::Markdown, OutputFormat::MarkdownGithub],
+            ..Scout::default()
+        };
+
+        // When
+        let result = run_scout(scout_opts);
+
+        // Then
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_output_path_parent_directories_are_created() -> Result<()> {
+        // Given
+        let output_file = "reports-nested/audit/report.json";
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            output_format: vec![OutputFormat::RawJson],
+            output_path: Some(PathBuf::from(output_file)),
+            ..Scout::default()
+        };
+
+        // When
+        run_scout(scout_opts)?;
+        let exists = Path::new(output_file).exists();
+        fs::remove_dir_all("reports-nested")?;
+
+        // Then
+        assert!(exists);
+        Ok(())
+    }
+
+    #[test]
+    fn test_output_path_expands_env_vars() -> Result<()> {
+        // Given
+        std::env::set_var("SCOUT_TEST_REPORT_NAME", "expanded");
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            output_format: vec![OutputFormat::Json],
+            output_path: Some(PathBuf::from("reports-env/${SCOUT_TEST_REPORT_NAME}.json")),
+            ..Scout::default()
+        };
+
+        // When
+        run_scout(scout_opts)?;
+        let exists = Path::new("reports-env/expanded.json").exists();
+        fs::remove_dir_all("reports-env")?;
+
+        // Then
+        assert!(exists);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fail_fast_reports_crates_that_do_not_compile() -> Result<()> {
+        // Given
+        let contract_dir = get_soroban_contract()
+            .parent()
+            .context("The contract has no directory")?
+            .to_path_buf();
+        let broken_dir = tempfile::tempdir()?;
+        fs::create_dir(broken_dir.path().join("src"))?;
+        for file in ["Cargo.toml", "Cargo.lock"] {
+            fs::copy(contract_dir.join(file), broken_dir.path().join(file))?;
+        }
+        let source = fs::read_to_string(contract_dir.join("src").join("lib.rs"))?;
+        fs::write(
+            broken_dir.path().join("src").join("lib.rs"),
+            source + "\ncompile_error!(\"broken on purpose\");\n",
+        )?;
+        let scout_opts = Scout {
+            manifest_path: Some(broken_dir.path().join("Cargo.toml")),
+            fail_fast: true,
+            ..Scout::default()
+        };
+
+        // When
+        let result = run_scout(scout_opts);
+
+        // Then
+        let error = result.expect_err("A crate that doesn't compile should fail the run");
+        assert!(error
+            .to_string()
+            .starts_with("1 crate failed to compile: soroban_contract"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_failing_post_hook_fails_the_run() {
+        // Given
+        let output_file = "report-post-hook.json";
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            output_format: vec![OutputFormat::Json],
+            output_path: Some(PathBuf::from(output_file)),
+            post_hook: Some("exit 3".to_string()),
+            ..Scout::default()
+        };
+
+        // When
+        let result = run_scout(scout_opts);
+        let _ = fs::remove_file(output_file);
+
+        // Then
+        assert!(result.is_err_and(|err| err.to_string().contains("Post hook")));
+    }
+
+    #[test]
+    fn test_stats_leave_streamed_ndjson_intact() -> Result<()> {
+        // Given
+        let manifest_path = get_soroban_contract();
+
+        // When
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_cargo-scout-audit"))
+            .arg("scout-audit")
+            .arg("--manifest-path")
+            .arg(&manifest_path)
+            .args(["--output-format", "ndjson", "--output-path", "-", "--stats"])
+            .output()?;
+        let stdout = String::from_utf8(output.stdout)?;
+        let stderr = String::from_utf8(output.stderr)?;
+
+        // Then
+        for line in stdout.lines().filter(|line| !line.trim().is_empty()) {
+            serde_json::from_str::<Value>(line)
+                .with_context(|| format!("Not a JSON line in the stream: {line}"))?;
+        }
+        assert!(stderr.contains("Elapsed time:"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_finding_request_matches_the_detectors_body() -> Result<()> {
+        // Given
+        let body = r#"{ "crate": "my_contract", "message": { "code": { "code": "divide_before_multiply" } } }"#;
+
+        // When
+        let finding: FindingRequest = serde_json::from_str(body)?;
+
+        // Then
+        assert_eq!(finding.krate, "my_contract");
+        assert_eq!(finding.message["code"]["code"], "divide_before_multiply");
+        assert_eq!(finding.kind, FindingKind::Diagnostic);
+        Ok(())
+    }
+
+    #[test]
+    fn test_finding_request_reads_unstructured_messages() -> Result<()> {
+        // Given
+        let body = r#"{ "kind": "message", "crate": "my_contract", "message": "thread panicked", "timestamp": 1700000000000 }"#;
+
+        // When
+        let finding: FindingRequest = serde_json::from_str(body)?;
+
+        // Then
+        assert_eq!(finding.kind, FindingKind::Message);
+        assert_eq!(finding.message, "thread panicked");
+        assert_eq!(finding.timestamp, Some(1700000000000));
+        Ok(())
+    }
+
+    #[test]
+    fn test_only_crates_without_matches_is_rejected() {
+        // Given
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            only_crates: vec!["no-such-crate-*".to_string()],
+            ..Scout::default()
+        };
+
+        // When
+        let result = run_scout(scout_opts);
+
+        // Then
+        assert!(result.is_err_and(|err| err.to_string().contains("--only-crates")));
+    }
+
+    #[test]
+    fn test_merge_deduplicates_raw_reports() -> Result<()> {
+        // Given
+        let shard = "report-shard.json";
+        let merged = "report-merged.json";
+        run_scout(Scout {
+            manifest_path: Some(get_soroban_contract()),
+            output_format: vec![OutputFormat::RawJson],
+            output_path: Some(PathBuf::from(shard)),
+            ..Scout::default()
+        })?;
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            output_format: vec![OutputFormat::RawJson],
+            output_path: Some(PathBuf::from(merged)),
+            merge: vec![PathBuf::from(shard), PathBuf::from(shard)],
+            ..Scout::default()
+        };
+
+        // When
+        run_scout(scout_opts)?;
+        let shard_findings = fs::read_to_string(shard)?.lines().count();
+        let merged_findings = fs::read_to_string(merged)?.lines().count();
+        fs::remove_file(shard)?;
+        fs::remove_file(merged)?;
+
+        // Then
+        assert!(shard_findings > 0);
+        assert_eq!(merged_findings, shard_findings);
+        Ok(())
+    }
+
+    #[test]
+    fn test_markdown_uses_report_title_and_author() -> Result<()> {
+        // Given
+        let output_file = "report-branded.md";
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            output_format: vec![OutputFormat::Markdown],
+            output_path: Some(PathBuf::from(output_file)),
+            report_title: Some("Token Audit".to_string()),
+            report_author: Some("Acme Security".to_string()),
+            ..Scout::default()
+        };
+
+        // When
+        run_scout(scout_opts)?;
+        let markdown = fs::read_to_string(output_file)?;
+        fs::remove_file(output_file)?;
+
+        // Then
+        assert!(markdown.contains("# Scout Report - Token Audit"));
+        assert!(markdown.contains("Prepared by Acme Security"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sarif_records_the_tool_version() -> Result<()> {
+        // Given
+        let output_file = "report-provenance.sarif";
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            output_format: vec![OutputFormat::Sarif],
+            output_path: Some(PathBuf::from(output_file)),
+            ..Scout::default()
+        };
+
+        // When
+        run_scout(scout_opts)?;
+        let sarif: Value = serde_json::from_str(&fs::read_to_string(output_file)?)?;
+        fs::remove_file(output_file)?;
+
+        // Then
+        let driver = &sarif["runs"][0]["tool"]["driver"];
+        assert_eq!(driver["version"], env!("CARGO_PKG_VERSION"));
+        assert!(driver["properties"]["detectorsRef"].is_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_embeds_metadata() -> Result<()> {
+        // Given
+        let output_file = "report-metadata.json";
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            output_format: vec![OutputFormat::Json],
+            output_path: Some(PathBuf::from(output_file)),
+            embed_metadata: true,
+            ..Scout::default()
+        };
+
+        // When
+        run_scout(scout_opts)?;
+        let report: Value = serde_json::from_str(&fs::read_to_string(output_file)?)?;
+        fs::remove_file(output_file)?;
+
+        // Then
+        let metadata = &report["metadata"];
+        assert_eq!(metadata["scout_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(metadata["toolchains"][0]["blockchain"], "Soroban");
+        assert!(metadata["detectors"]
+            .as_object()
+            .is_some_and(|detectors| !detectors.is_empty()));
+        let coverage = &report["detector_coverage"];
+        assert!(coverage["used"].as_u64() <= coverage["available"].as_u64());
+        assert!(coverage["used"].as_u64() > Some(0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_summary_only() -> Result<()> {
+        // Given
+        let output_file = "report-summary.json";
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            output_format: vec![OutputFormat::Json],
+            output_path: Some(PathBuf::from(output_file)),
+            summary_only: true,
+            ..Scout::default()
+        };
+
+        // When
+        run_scout(scout_opts)?;
+        let report: Value = serde_json::from_str(&fs::read_to_string(output_file)?)?;
+        fs::remove_file(output_file)?;
+
+        // Then
+        assert!(report.get("findings").is_none());
+        assert!(report["categories"]
+            .as_array()
+            .is_some_and(|categories| categories
+                .iter()
+                .all(|category| category["results_count"].is_u64())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_severity_map_overrides_defaults() -> Result<()> {
+        // Given
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("severity-map.json");
+        fs::write(&path, r#"{ "sarif": { "medium": "error" } }"#)?;
+
+        // When
+        let map = SeverityMap::from_file(&path)?;
+
+        // Then
+        assert_eq!(map.sarif_level("Medium"), "error");
+        assert_eq!(map.sarif_level("Minor"), "note");
+        assert_eq!(map.codeclimate_severity("Critical"), "critical");
+
+        fs::write(&path, r#"{ "sarif": { "medium": "fatal" } }"#)?;
+        assert!(SeverityMap::from_file(&path).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_severity_map_resolves_custom_labels() -> Result<()> {
+        // Given
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("severity-map.json");
+        fs::write(
+            &path,
+            r#"{ "labels": { "High": "critical", "low": "minor" } }"#,
+        )?;
+
+        // When
+        let map = SeverityMap::from_file(&path)?;
+
+        // Then
+        assert_eq!(map.severity("high"), Some(Severity::Critical));
+        assert_eq!(map.severity("MEDIUM"), Some(Severity::Medium));
+        assert_eq!(map.sarif_level("Low"), "note");
+        assert_eq!(map.severity("urgent"), None);
+        assert!(Severity::Critical < Severity::Enhancement);
+
+        fs::write(&path, r#"{ "labels": { "medium": "critical" } }"#)?;
+        assert!(SeverityMap::from_file(&path).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_severity_map_reorders_severities() -> Result<()> {
+        // Given
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("severity-map.json");
+        fs::write(
+            &path,
+            r#"{ "order": ["medium", "Critical", "enhancement", "minor"] }"#,
+        )?;
+
+        // When
+        let map = SeverityMap::from_file(&path)?;
+
+        // Then
+        assert_eq!(map.rank(Severity::Medium), 0);
+        assert_eq!(map.rank(Severity::Minor), 3);
+        assert_eq!(
+            map.ordered(),
+            [
+                Severity::Medium,
+                Severity::Critical,
+                Severity::Enhancement,
+                Severity::Minor
+            ]
+        );
+        assert_eq!(SeverityMap::default().ordered(), Severity::ALL);
+
+        fs::write(&path, r#"{ "order": ["medium", "critical", "minor"] }"#)?;
+        assert!(SeverityMap::from_file(&path).is_err());
+        fs::write(
+            &path,
+            r#"{ "order": ["medium", "medium", "critical", "minor"] }"#,
+        )?;
+        assert!(SeverityMap::from_file(&path).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_vulnerability_lists_only_the_catalog_ids_it_has() -> Result<()> {
+        // Given
+        let mapped = LintInfo {
+            id: "divide_before_multiply".to_string(),
+            cwe: Some("CWE-682".to_string()),
+            ..LintInfo::default()
+        };
+        let unmapped = LintInfo {
+            id: "unsafe_unwrap".to_string(),
+            ..LintInfo::default()
+        };
+
+        // When
+        let mapped = serde_json::to_value(Vulnerability::from(&mapped))?;
+        let unmapped = serde_json::to_value(Vulnerability::from(&unmapped))?;
+
+        // Then
+        assert_eq!(mapped["cwe"], "CWE-682");
+        assert!(mapped.get("swc").is_none());
+        assert!(unmapped.get("cwe").is_none());
+        assert!(unmapped.get("swc").is_none());
+        Ok(())
+    }
+
+    fn raw_finding(id: &str, file: &str, line: u64, krate: &str) -> Value {
+        serde_json::json!({
+            "code": { "code": id },
+            "message": format!("{id} found"),
+            "crate": krate,
+            "spans": [{
+                "file_name": file,
+                "line_start": line,
+                "column_start": 1,
+                "line_end": line,
+                "column_end": 10,
+            }],
+        })
+    }
+
+    fn detector_names() -> Vec<String> {
+        ["unsafe-unwrap", "unsafe-expect", "divide-before-multiply"]
+            .map(String::from)
+            .to_vec()
+    }
+
+    #[test]
+    fn test_glob_regex_matches_paths() -> Result<()> {
+        // Given
+        let any_depth = glob_regex("**/tests/*.rs")?;
+        let one_char = glob_regex("src/lib?.rs")?;
+
+        // When / Then
+        assert!(any_depth.is_match("tests/main.rs"));
+        assert!(any_depth.is_match("crates/token/tests/main.rs"));
+        assert!(!any_depth.is_match("tests/nested/main.rs"));
+        assert!(one_char.is_match("src/lib1.rs"));
+        assert!(!one_char.is_match("src/lib.rs"));
+        assert!(!one_char.is_match("src/lib/.rs"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_expands_globs_without_duplicates() -> Result<()> {
+        // Given
+        let names = detector_names();
+
+        // When
+        let filtered = get_filtered_detectors("unsafe-*,unsafe_unwrap", &names, false)?;
+
+        // Then
+        assert_eq!(filtered, vec!["unsafe-unwrap", "unsafe-expect"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_rejects_unknown_detectors_and_unmatched_globs_in_strict_mode() {
+        // Given
+        let names = detector_names();
+
+        // When / Then
+        assert!(get_filtered_detectors("missing-detector", &names, false).is_err());
+        assert!(get_filtered_detectors("missing-*", &names, false).is_ok_and(|d| d.is_empty()));
+        assert!(get_filtered_detectors("missing-*", &names, true).is_err());
+    }
+
+    #[test]
+    fn test_unknown_excluded_detector_warns_unless_strict() -> Result<()> {
+        // Given
+        let names = detector_names();
+
+        // When
+        let kept = get_excluded_detectors("unsafe-*,missing-detector", &names, false)?;
+
+        // Then
+        assert_eq!(kept, vec!["divide-before-multiply"]);
+        assert!(get_excluded_detectors("missing-detector", &names, true).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedup_findings_merges_crates() {
+        // Given
+        let findings = vec![
+            raw_finding("unsafe_unwrap", "src/lib.rs", 3, "token"),
+            raw_finding("unsafe_unwrap", "src/lib.rs", 3, "token_tests"),
+            raw_finding("unsafe_unwrap", "src/lib.rs", 8, "token"),
+        ];
+
+        // When
+        let deduped = dedup_findings(&findings);
+
+        // Then
+        assert_eq!(deduped.len(), 2);
+        assert_eq!(
+            deduped[0]["crates"],
+            serde_json::json!(["token", "token_tests"])
+        );
+        assert_eq!(deduped[1]["crates"], serde_json::json!(["token"]));
+    }
+
+    #[test]
+    fn test_compare_to_splits_new_fixed_and_unchanged() {
+        // Given
+        let previous = vec![
+            raw_finding("unsafe_unwrap", "src/lib.rs", 3, "token"),
+            raw_finding("unsafe_expect", "src/lib.rs", 5, "token"),
+        ];
+        let current = vec![
+            raw_finding("unsafe_unwrap", "src/lib.rs", 3, "token"),
+            raw_finding("divide_before_multiply", "src/lib.rs", 9, "token"),
+        ];
+
+        // When
+        let diff = diff_findings(&previous, &current, Path::new("previous.json"));
+
+        // Then
+        let ids = |entries: &[cargo_scout_audit::output::report::DiffEntry]| {
+            entries
+                .iter()
+                .map(|entry| entry.vulnerability_id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&diff.new), vec!["divide_before_multiply"]);
+        assert_eq!(ids(&diff.fixed), vec!["unsafe_expect"]);
+        assert_eq!(ids(&diff.unchanged), vec!["unsafe_unwrap"]);
+        assert_eq!(diff.compared_to, "previous.json");
+    }
+
+    #[test]
+    fn test_changed_files_keep_only_their_findings() {
+        // Given
+        let workspace_root = Path::new("/nonexistent-workspace");
+        let changed = HashSet::from([workspace_root.join("src").join("lib.rs")]);
+        let findings = vec![
+            raw_finding("unsafe_unwrap", "src/lib.rs", 3, "token"),
+            raw_finding("unsafe_unwrap", "src/storage.rs", 3, "token"),
+        ];
+
+        // When
+        let kept = filter_changed_findings(findings, &changed, workspace_root);
+
+        // Then
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0]["spans"][0]["file_name"], "src/lib.rs");
+    }
+
+    #[test]
+    fn test_ignored_paths_drop_matching_findings() -> Result<()> {
+        // Given
+        let workspace_root = Path::new("/nonexistent-workspace");
+        let findings = vec![
+            raw_finding("unsafe_unwrap", "src/lib.rs", 3, "token"),
+            raw_finding(
+                "unsafe_unwrap",
+                "/nonexistent-workspace/tests/it.rs",
+                3,
+                "token",
+            ),
+            raw_finding("unsafe_unwrap", "vendor/dep/src/lib.rs", 3, "token"),
+        ];
+
+        // When
+        let kept = filter_ignored_paths(
+            findings,
+            &["tests/**".to_string(), "vendor/**/*.rs".to_string()],
+            workspace_root,
+        )?;
+
+        // Then
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0]["spans"][0]["file_name"], "src/lib.rs");
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_status_is_incomplete_when_a_crate_fails_to_compile() {
+        // Given
+        let compiled = HashMap::from([("token".to_string(), true)]);
+        let broken = HashMap::from([("token".to_string(), true), ("vault".to_string(), false)]);
+        let no_findings: [Value; 0] = [];
+
+        // When / Then
+        assert_eq!(
+            ReportStatus::from_findings(&no_findings, &compiled),
+            ReportStatus::Clean
+        );
+        assert_eq!(
+            ReportStatus::from_findings(&no_findings, &broken),
+            ReportStatus::Incomplete
+        );
+        assert_eq!(
+            ReportStatus::from_findings(&no_findings, &HashMap::new()),
+            ReportStatus::Incomplete
+        );
+    }
+
+    #[test]
+    fn test_min_confidence_drops_less_confident_findings() {
+        // Given
+        let detectors_info: HashMap<String, LintInfo> =
+            [("sure", Confidence::High), ("guess", Confidence::Low)]
+                .map(|(id, confidence)| {
+                    let info = LintInfo {
+                        id: id.to_string(),
+                        confidence,
+                        ..LintInfo::default()
+                    };
+                    (id.to_string(), info)
+                })
+                .into();
+        let finding = |id: &str| serde_json::json!({ "code": { "code": id } });
+
+        // When
+        let kept = filter_by_confidence(
+            vec![finding("sure"), finding("guess"), finding("unknown")],
+            Confidence::Medium,
+            &detectors_info,
+        );
+
+        // Then
+        assert_eq!(kept, vec![finding("sure"), finding("unknown")]);
+    }
+
+    #[test]
+    fn test_ignore_comment_suppresses_finding() -> Result<()> {
+        // Given
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("lib.rs"),
+            "// scout-audit:ignore divide-before-multiply checked by hand\nlet x = a / b * c;\nlet y = a / b * c;\n",
+        )?;
+        let finding = |line: u64| {
+            serde_json::json!({
+                "code": { "code": "divide_before_multiply" },
+                "message": "Division before multiplication might result in a loss of precision",
+                "spans": [{ "file_name": "lib.rs", "line_start": line }],
+            })
+        };
+
+        // When
+        let (kept, suppressed) = split_suppressed(vec![finding(2), finding(3)], dir.path());
+
+        // Then
+        assert_eq!(kept, vec![finding(3)]);
+        assert_eq!(suppressed.len(), 1);
+        assert_eq!(suppressed[0].location, "lib.rs:2");
+        assert_eq!(suppressed[0].reason.as_deref(), Some("checked by hand"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lock_flags_are_read_from_cargo_args() {
+        // Given
+        let args = ["--frozen", "--release", "--", "--offline"].map(String::from);
+
+        // When
+        let flags = LockFlags::from_args(&args);
+
+        // Then
+        assert!(flags.locked() && flags.offline());
+        assert_eq!(flags.args(), vec!["--frozen"]);
+    }
+
+    #[test]
+    fn test_analyze_returns_findings() -> Result<()> {
+        // Given
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            ..Scout::default()
+        };
+
+        // When
+        let Analysis::Findings(result) = analyze(scout_opts)? else {
+            bail!("The analysis should return findings");
+        };
+
+        // Then
+        assert!(!result.findings.is_empty());
+        assert!(!result.detectors_info.is_empty());
+        assert!(result.crates.values().all(|&success| success));
+        assert!(!result.project_info.packages.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_returns_the_output_of_informational_options() -> Result<()> {
+        // Given
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            toolchain: true,
+            ..Scout::default()
+        };
+
+        // When
+        let analysis = analyze(scout_opts)?;
+
+        // Then
+        let Analysis::Output(toolchain) = analysis else {
+            bail!("--toolchain should return its output instead of findings");
+        };
+        assert!(toolchain.starts_with("nightly-"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_nightly_child_receives_all_options() -> Result<()> {
+        // Given
+        let output_file = std::env::current_dir()?.join("report-nightly-child.json");
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            output_format: vec![OutputFormat::Json],
+            output_path: Some(output_file.clone()),
+            filter: Some("divide_before_multiply".to_string()),
+            ..Scout::default()
+        };
+        let command = nightly_command("nightly-1970-01-01", &scout_opts)?
+            .context("A non-matching toolchain should re-run scout")?;
+        let options = command
+            .get_envs()
+            .find(|(key, _)| *key == "SCOUT_NIGHTLY_OPTIONS")
+            .and_then(|(_, value)| value)
+            .context("Options should be forwarded to the child")?
+            .to_os_string();
+
+        // When
+        // The child gets no flags at all, so whatever it honors came through the forwarded options
+        let status = std::process::Command::new(env!("CARGO_BIN_EXE_cargo-scout-audit"))
+            .arg("scout-audit")
+            .env("SCOUT_NIGHTLY_OPTIONS", options)
+            .status()?;
+        let report = fs::read_to_string(&output_file);
+        let _ = fs::remove_file(&output_file);
+
+        // Then
+        assert!(status.success(), "The child should run: {status}");
+        let report: Value = serde_json::from_str(&report?)?;
+        let detectors = report["findings"]
+            .as_array()
+            .context("The child should write a JSON report")?
+            .iter()
+            .map(|finding| finding["vulnerability_id"].as_str().unwrap_or_default())
+            .collect::<HashSet<_>>();
+        assert_eq!(detectors, HashSet::from(["divide_before_multiply"]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_file_crate_uses_detected_sdk() -> Result<()> {
+        // Given
+        let file = get_soroban_contract().with_file_name("src").join("lib.rs");
+
+        // When
+        let dir = synthesize_crate(&file, None)?;
+        let manifest = fs::read_to_string(dir.path().join("Cargo.toml"))?;
+
+        // Then
+        assert!(manifest.contains("soroban-sdk"));
+        assert!(dir.path().join("src").join("lib.rs").exists());
+        Ok(())
+    }
+
     fn test_output_format(output_file: &str, format: &OutputFormat) -> Result<()> {
         // For debugging purposes
         let output_format = format.clone();
@@ -298,6 +1133,23 @@ mod tests {
         check_counts(&counts, &expected);
     }
 
+    #[test]
+    fn test_denied_detector_fails_the_run() {
+        // Given
+        let scout_opts = Scout {
+            manifest_path: Some(get_soroban_contract()),
+            deny: vec!["divide-before-multiply".to_string()],
+            ..Scout::default()
+        };
+
+        // When
+        let result = run_scout(scout_opts);
+
+        // Then
+        let err = result.expect_err("A finding of a denied detector should fail the run");
+        assert!(err.to_string().contains("divide_before_multiply"));
+    }
+
     fn count_strings(strings: &[Option<String>]) -> Option<HashMap<String, usize>> {
         let mut ret = HashMap::<String, usize>::new();
         for i in strings.iter() {
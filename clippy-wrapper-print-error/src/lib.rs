@@ -2,6 +2,18 @@
 use capture_stdio::Capture;
 use std::io::BufRead;
 
+// Written after the callback's output, to read all of it back without waiting for EOF
+const END_OF_OUTPUT: &str = "\u{1}scout-end-of-output\u{1}";
+
+// Older servers only set the port and always listen on 127.0.0.1
+fn server_address(port: &str) -> Option<std::net::SocketAddr> {
+    let address = std::env::var("SCOUT_SERVER_ADDRESS")
+        .ok()
+        .and_then(|address| address.parse::<std::net::IpAddr>().ok())
+        .unwrap_or(std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST));
+    Some(std::net::SocketAddr::new(address, port.parse().ok()?))
+}
+
 pub fn print_error<F: FnOnce()>(cb: F) {
     let port = std::env::var("SCOUT_PORT_NUMBER");
 
@@ -10,6 +22,12 @@ pub fn print_error<F: FnOnce()>(cb: F) {
         return;
     }
 
+    // A stale SCOUT_PORT_NUMBER must not swallow the diagnostic
+    let Some(address) = server_address(port.as_deref().unwrap_or_default()) else {
+        cb();
+        return;
+    };
+
     let pipe_result = capture_stdio::PipedStderr::capture();
     if pipe_result.is_err() {
         cb();
@@ -19,36 +37,62 @@ pub fn print_error<F: FnOnce()>(cb: F) {
     let old = std::io::set_output_capture(None);
     let mut piped_stderr = pipe_result.unwrap();
 
-    let port = port.unwrap();
-
     cb();
 
     let _ = std::io::set_output_capture(old);
     let mut captured = String::new();
     let mut buf_reader = std::io::BufReader::new(piped_stderr.get_reader());
-    eprintln!(""); // sometimes output contains new-line at end and sometimes not. nevertheless it
-                   // is required for us, here!
-    let _ = buf_reader.read_line(&mut captured);
+    // The output may or may not end with a new line, so the marker gets a line of its own
+    eprintln!("\n{END_OF_OUTPUT}");
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match buf_reader.read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) if line.trim_end() == END_OF_OUTPUT => break,
+            Ok(_) => captured.push_str(&line),
+        }
+    }
+    // Gives stderr back, for the fallback below to print to it
+    drop(buf_reader);
+    drop(piped_stderr);
     captured = captured.trim().to_string();
 
     let krate = std::env::var("CARGO_CRATE_NAME");
     let krate = krate.unwrap_or_default();
 
-    let body = {
-        let json = serde_json::from_str::<serde_json::Value>(&captured);
-        if let Ok(json) = json {
-            serde_json::json!({
-                "crate": krate,
-                "message": json,
-            })
-            .to_string()
-        } else {
-            captured
-        }
-    };
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default();
 
-    let _ = reqwest::blocking::Client::new()
-        .post(format!("http://127.0.0.1:{port}/vuln"))
+    // The server tells diagnostics apart from anything else by `kind`
+    let body = match serde_json::from_str::<serde_json::Value>(&captured) {
+        Ok(json) => serde_json::json!({
+            "kind": "diagnostic",
+            "crate": krate,
+            "message": json,
+            "timestamp": timestamp,
+        }),
+        Err(_) => serde_json::json!({
+            "kind": "message",
+            "crate": krate,
+            "message": captured,
+            "timestamp": timestamp,
+        }),
+    }
+    .to_string();
+
+    let sent = reqwest::blocking::Client::new()
+        .post(format!("http://{address}/vuln"))
         .body(body)
-        .send();
+        .send()
+        .and_then(|response| response.error_for_status());
+    // Without a server to take it, the diagnostic goes to stderr after all
+    if let Err(e) = sent {
+        eprintln!(
+            "scout server at {address} is unreachable ({e}), printing the diagnostic instead"
+        );
+        eprintln!("{captured}");
+    }
 }
@@ -213,7 +213,13 @@ pub const DYLINT_VERSION: &str = "0.1.0";
 
 pub use paste;
 
+/// What a detector tells scout about itself. Scout owns the struct and sets `size` to the bytes
+/// it has room for, so a detector only fills in the fields that fit and then leaves in `size`
+/// the bytes it filled in. A detector built against another version of this crate can then
+/// neither write past the end of scout's struct nor leave fields scout reads uninitialized.
+#[repr(C)]
 pub struct LintInfo {
+    pub size: usize,
     pub id: ffi::CString,
     pub name: ffi::CString,
     pub short_message: ffi::CString,
@@ -221,6 +227,12 @@ pub struct LintInfo {
     pub severity: ffi::CString,
     pub help: ffi::CString,
     pub vulnerability_class: ffi::CString,
+    pub doc_url: ffi::CString,
+    // One of `high`, `medium` or `low`; left empty it means `high`
+    pub confidence: ffi::CString,
+    // Identifiers such as `CWE-682` or `SWC-101`; left empty when the detector maps to none
+    pub cwe: ffi::CString,
+    pub swc: ffi::CString,
 }
 
 // smoelius: Including `extern crate rustc_driver` causes the library to link against
@@ -284,15 +296,37 @@ macro_rules! __raw_lint_info {
         long_message: $long_message:expr,
         severity: $severity:expr,
         help: $help:expr,
-        vulnerability_class: $vulnerability_class:expr $(,)*
+        vulnerability_class: $vulnerability_class:expr
+        $(, doc_url: $doc_url:expr)?
+        $(, confidence: $confidence:expr)?
+        $(, cwe: $cwe:expr)?
+        $(, swc: $swc:expr)? $(,)*
     }) => {
-        $VAR.id = std::ffi::CString::new(stringify!($NAME).to_lowercase().as_bytes()).unwrap();
-        $VAR.name = std::ffi::CString::new($name.as_bytes()).unwrap();
-        $VAR.short_message = std::ffi::CString::new($desc.as_bytes()).unwrap();
-        $VAR.long_message = std::ffi::CString::new($long_message.as_bytes()).unwrap();
-        $VAR.severity = std::ffi::CString::new($severity.as_bytes()).unwrap();
-        $VAR.help = std::ffi::CString::new($help.as_bytes()).unwrap();
-        $VAR.vulnerability_class = std::ffi::CString::new($vulnerability_class.as_bytes()).unwrap();
+        $crate::__set_lint_info_field!($VAR, id, stringify!($NAME).to_lowercase());
+        $crate::__set_lint_info_field!($VAR, name, $name);
+        $crate::__set_lint_info_field!($VAR, short_message, $desc);
+        $crate::__set_lint_info_field!($VAR, long_message, $long_message);
+        $crate::__set_lint_info_field!($VAR, severity, $severity);
+        $crate::__set_lint_info_field!($VAR, help, $help);
+        $crate::__set_lint_info_field!($VAR, vulnerability_class, $vulnerability_class);
+        $($crate::__set_lint_info_field!($VAR, doc_url, $doc_url);)?
+        $($crate::__set_lint_info_field!($VAR, confidence, $confidence);)?
+        $($crate::__set_lint_info_field!($VAR, cwe, $cwe);)?
+        $($crate::__set_lint_info_field!($VAR, swc, $swc);)?
+        $VAR.size = $VAR.size.min(std::mem::size_of::<$crate::LintInfo>());
+    };
+}
+
+// Only writes fields that lie within the `size` scout has room for. `offset_of!` isn't stable on
+// every toolchain detectors are built with, so the offset comes from the field's address.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __set_lint_info_field {
+    ($VAR: ident, $field:ident, $value: expr) => {
+        let offset = std::ptr::addr_of!($VAR.$field) as usize - std::ptr::addr_of!(*$VAR) as usize;
+        if offset + std::mem::size_of::<std::ffi::CString>() <= $VAR.size {
+            $VAR.$field = std::ffi::CString::new($value.as_bytes()).unwrap();
+        }
     };
 }
 